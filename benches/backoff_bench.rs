@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+use wg_utils::lfs::Backoff;
+
+// successful increments per thread per measured iteration
+const INCREMENTS: usize = 20_000;
+
+const THREADS: [usize; 3] = [1, 2, 4];
+
+// what a thread does after losing a race
+type Wait = fn(&mut Backoff);
+
+// Hammers one counter with compare-exchange increments from `threads`
+// threads, calling `wait` with a per-loop `Backoff` after every lost race.
+fn contend(threads: usize, wait: Wait) -> Duration {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(threads + 1));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let (counter, barrier) = (Arc::clone(&counter), Arc::clone(&barrier));
+            thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..INCREMENTS {
+                    let mut backoff = Backoff::new();
+                    let mut current = counter.load(Ordering::Relaxed);
+                    while let Err(actual) = counter.compare_exchange_weak(
+                        current,
+                        current + 1,
+                        Ordering::AcqRel,
+                        Ordering::Relaxed,
+                    ) {
+                        current = actual;
+                        wait(&mut backoff);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let start = Instant::now();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    start.elapsed()
+}
+
+fn benchmark_backoff(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cas_contention");
+    group.sample_size(10);
+
+    let strategies: [(&str, Wait); 2] = [
+        ("spin_loop", |_| core::hint::spin_loop()),
+        ("backoff", Backoff::snooze),
+    ];
+
+    for threads in THREADS {
+        group.throughput(Throughput::Elements((threads * INCREMENTS) as u64));
+        for (name, wait) in strategies {
+            group.bench_with_input(BenchmarkId::new(name, threads), &threads, |bench, &n| {
+                bench.iter_custom(|iters| (0..iters).map(|_| contend(n, wait)).sum())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_backoff);
+criterion_main!(benches);
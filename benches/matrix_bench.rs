@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use wg_utils::math::matrix::matrix::Matrix;
+use wg_utils::math::matrix::ops::mul;
+use wg_utils::math::matrix::utils::error::Error;
+
+const SIZES: [usize; 4] = [64, 256, 512, 1024];
+
+type MulFn = fn(&Matrix, &Matrix) -> Result<Matrix, Error>;
+
+// each multiply variant is benchmarked over every size. Only the naive `mul`
+// exists so far: `mul_blocked`, `mul_strassen` and `mul_parallel` are not in
+// `ops` yet, so this is a baseline, not the comparison. Each one only needs
+// an entry here once it lands.
+const VARIANTS: [(&str, MulFn); 1] = [("mul", mul)];
+
+fn square(n: usize, seed: f64) -> Matrix {
+    let data = (0..n * n)
+        .map(|i| ((i as f64 + seed) * 0.37).sin())
+        .collect();
+    Matrix::new(n, n, data)
+}
+
+fn benchmark_mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_mul");
+    // a naive 1024^3 multiply takes seconds per iteration
+    group.sample_size(10);
+
+    for n in SIZES {
+        let (a, b) = (square(n, 0.0), square(n, 1.0));
+        for (name, f) in VARIANTS {
+            group.bench_with_input(BenchmarkId::new(name, n), &n, |bench, _| {
+                bench.iter(|| f(black_box(&a), black_box(&b)).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_mul);
+criterion_main!(benches);
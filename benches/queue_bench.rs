@@ -0,0 +1,115 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+use wg_utils::lfs::queue::{BoundedLockFreeQueue, LockFreeQueue};
+
+// items moved through the queue per measured iteration
+const ITEMS: usize = 100_000;
+
+// (producers, consumers)
+const CONFIGS: [(usize, usize); 5] = [(1, 1), (2, 1), (4, 1), (2, 2), (4, 4)];
+
+// Moves `ITEMS` values from `producers` threads to `consumers` threads and
+// returns the wall time from the common start to the last item received.
+fn exchange<Q: Send + Sync + 'static>(
+    queue: Arc<Q>,
+    producers: usize,
+    consumers: usize,
+    push: fn(&Q, usize),
+    pop: fn(&Q) -> Option<usize>,
+) -> Duration {
+    let barrier = Arc::new(Barrier::new(producers + consumers + 1));
+    let received = Arc::new(AtomicUsize::new(0));
+
+    let mut handles = Vec::new();
+    for p in 0..producers {
+        let (queue, barrier) = (Arc::clone(&queue), Arc::clone(&barrier));
+        handles.push(thread::spawn(move || {
+            barrier.wait();
+            for i in (p..ITEMS).step_by(producers) {
+                push(&queue, i);
+            }
+        }));
+    }
+    for _ in 0..consumers {
+        let (queue, barrier) = (Arc::clone(&queue), Arc::clone(&barrier));
+        let received = Arc::clone(&received);
+        handles.push(thread::spawn(move || {
+            barrier.wait();
+            while received.load(Ordering::Relaxed) < ITEMS {
+                match pop(&queue) {
+                    Some(_) => {
+                        received.fetch_add(1, Ordering::Relaxed);
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+        }));
+    }
+
+    barrier.wait();
+    let start = Instant::now();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    start.elapsed()
+}
+
+fn benchmark_queues(c: &mut Criterion) {
+    let mut group = c.benchmark_group("queue_contention");
+    group.throughput(Throughput::Elements(ITEMS as u64));
+    group.sample_size(10);
+
+    for (producers, consumers) in CONFIGS {
+        let id = format!("{producers}p{consumers}c");
+
+        // `LockFreeQueue` frees nodes on dequeue without a reclamation
+        // scheme, so any second thread (producer or consumer) may touch a
+        // node the other side already freed. Only 1p1c is measured until
+        // reclamation lands.
+        if (producers, consumers) == (1, 1) {
+            group.bench_function(BenchmarkId::new("unbounded", &id), |bench| {
+                bench.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| {
+                            exchange(
+                                Arc::new(LockFreeQueue::new()),
+                                producers,
+                                consumers,
+                                |q, v| q.enqueue(v),
+                                |q| q.dequeue(),
+                            )
+                        })
+                        .sum()
+                })
+            });
+        }
+
+        group.bench_function(BenchmarkId::new("bounded_1024", &id), |bench| {
+            bench.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        exchange(
+                            Arc::new(BoundedLockFreeQueue::new(1024)),
+                            producers,
+                            consumers,
+                            |q, mut v| {
+                                while let Err(back) = q.enqueue(v) {
+                                    v = back;
+                                    thread::yield_now();
+                                }
+                            },
+                            |q| q.dequeue(),
+                        )
+                    })
+                    .sum()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_queues);
+criterion_main!(benches);
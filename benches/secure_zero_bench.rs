@@ -0,0 +1,27 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::ptr;
+use std::sync::atomic::{fence, Ordering};
+use wg_utils::r#unsafe::mem::secure_zero_memory;
+
+const LEN: usize = 4 << 20;
+
+// the previous implementation, one volatile byte store at a time
+unsafe fn secure_zero_bytewise(ptr: *mut u8, count: usize) {
+    for i in 0..count {
+        ptr::write_volatile(ptr.add(i), 0);
+    }
+    fence(Ordering::SeqCst);
+}
+
+fn benchmark_secure_zero(c: &mut Criterion) {
+    let mut buf = vec![0xaau8; LEN];
+    c.bench_function("secure_zero_memory_4mib", |bench| {
+        bench.iter(|| unsafe { secure_zero_memory(black_box(buf.as_mut_ptr()), LEN) })
+    });
+    c.bench_function("secure_zero_bytewise_4mib", |bench| {
+        bench.iter(|| unsafe { secure_zero_bytewise(black_box(buf.as_mut_ptr()), LEN) })
+    });
+}
+
+criterion_group!(benches, benchmark_secure_zero);
+criterion_main!(benches);
@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use utils::lfs::stack::LockFreeStack;
+use wg_utils::lfs::stack::LockFreeStack;
 
 fn benchmark_push(c: &mut Criterion) {
     let stack = LockFreeStack::new();
@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wg_utils::math::vectorized::{add_slices, mul_slices, scale_slice};
+
+const LEN: usize = 4096;
+
+fn inputs() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let a = (0..LEN).map(|i| i as f64 * 0.5).collect();
+    let b = (0..LEN).map(|i| i as f64 + 1.0).collect();
+    (a, b, vec![0.0; LEN])
+}
+
+fn benchmark_add(c: &mut Criterion) {
+    let (a, b, mut out) = inputs();
+    c.bench_function("add_slices", |bench| {
+        bench.iter(|| add_slices(black_box(&a), black_box(&b), &mut out))
+    });
+    c.bench_function("add_scalar_loop", |bench| {
+        bench.iter(|| {
+            for ((o, x), y) in out.iter_mut().zip(black_box(&a)).zip(black_box(&b)) {
+                *o = x + y;
+            }
+        })
+    });
+}
+
+fn benchmark_mul(c: &mut Criterion) {
+    let (a, b, mut out) = inputs();
+    c.bench_function("mul_slices", |bench| {
+        bench.iter(|| mul_slices(black_box(&a), black_box(&b), &mut out))
+    });
+    c.bench_function("mul_scalar_loop", |bench| {
+        bench.iter(|| {
+            for ((o, x), y) in out.iter_mut().zip(black_box(&a)).zip(black_box(&b)) {
+                *o = x * y;
+            }
+        })
+    });
+}
+
+fn benchmark_scale(c: &mut Criterion) {
+    let (a, _, mut out) = inputs();
+    c.bench_function("scale_slice", |bench| {
+        bench.iter(|| scale_slice(black_box(&a), black_box(1.5), &mut out))
+    });
+    c.bench_function("scale_scalar_loop", |bench| {
+        bench.iter(|| {
+            let s = black_box(1.5);
+            for (o, x) in out.iter_mut().zip(black_box(&a)) {
+                *o = x * s;
+            }
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_add, benchmark_mul, benchmark_scale);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+fn cross(o: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Convex hull via Andrew's monotone chain.
+///
+/// Returns the hull vertices in counterclockwise order starting from the
+/// lowest-leftmost point. Collinear points on the hull's edges are dropped.
+pub fn convex_hull(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut pts = points.to_vec();
+    pts.sort_by(|a, b| a.partial_cmp(b).expect("points must not be NaN"));
+    pts.dedup();
+
+    if pts.len() < 3 {
+        return pts;
+    }
+
+    let mut hull: Vec<(f64, f64)> = Vec::with_capacity(pts.len() * 2);
+
+    for &p in &pts {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    let lower_len = hull.len() + 1;
+    for &p in pts.iter().rev().skip(1) {
+        while hull.len() >= lower_len && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    hull.pop(); // last point repeats the first
+    hull
+}
+
+/// Ray-casting point-in-polygon test. Points lying on an edge count as inside.
+pub fn point_in_polygon(point: (f64, f64), polygon: &[(f64, f64)]) -> bool {
+    let n = polygon.len();
+    if n < 3 {
+        return false;
+    }
+
+    let (px, py) = point;
+    let mut inside = false;
+    let mut j = n - 1;
+
+    for i in 0..n {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[j];
+
+        if on_segment(point, polygon[j], polygon[i]) {
+            return true;
+        }
+
+        if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+
+    inside
+}
+
+fn on_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> bool {
+    cross(a, b, p).abs() < 1e-9
+        && p.0 >= a.0.min(b.0)
+        && p.0 <= a.0.max(b.0)
+        && p.1 >= a.1.min(b.1)
+        && p.1 <= a.1.max(b.1)
+}
+
+/// Area of a simple polygon via the shoelace formula.
+pub fn polygon_area(polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let mut twice_area = 0.0;
+    for i in 0..n {
+        let (x1, y1) = polygon[i];
+        let (x2, y2) = polygon[(i + 1) % n];
+        twice_area += x1 * y2 - x2 * y1;
+    }
+
+    twice_area.abs() / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_with_interior_points() {
+        let points = [
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 0.0),
+            (0.5, 1.5),
+            (2.0, 2.0),
+            (1.0, 0.0),
+            (0.0, 2.0),
+            (1.5, 0.5),
+        ];
+
+        assert_eq!(
+            convex_hull(&points),
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_collinear_points() {
+        let points = [(3.0, 3.0), (0.0, 0.0), (1.0, 1.0), (2.0, 2.0)];
+        assert_eq!(convex_hull(&points), vec![(0.0, 0.0), (3.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(convex_hull(&[]).is_empty());
+        assert_eq!(convex_hull(&[(1.0, 2.0)]), vec![(1.0, 2.0)]);
+        assert_eq!(convex_hull(&[(1.0, 2.0), (1.0, 2.0)]), vec![(1.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+
+        assert!(point_in_polygon((0.5, 0.5), &square));
+        assert!(!point_in_polygon((1.5, 0.5), &square));
+        assert!(!point_in_polygon((-0.1, 0.9), &square));
+        assert!(point_in_polygon((1.0, 0.5), &square));
+        assert!(point_in_polygon((0.0, 0.0), &square));
+    }
+
+    #[test]
+    fn test_point_in_concave_polygon() {
+        let notch = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (2.0, 1.0), (0.0, 4.0)];
+
+        assert!(point_in_polygon((1.0, 1.0), &notch));
+        assert!(!point_in_polygon((2.0, 3.0), &notch));
+    }
+
+    #[test]
+    fn test_polygon_area() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        assert_eq!(polygon_area(&square), 1.0);
+
+        let clockwise: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(polygon_area(&clockwise), 1.0);
+
+        let triangle = [(0.0, 0.0), (4.0, 0.0), (0.0, 3.0)];
+        assert_eq!(polygon_area(&triangle), 6.0);
+    }
+}
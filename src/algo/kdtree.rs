@@ -0,0 +1,184 @@
+struct Node<const K: usize> {
+    point: [f64; K],
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// A static k-d tree over `K`-dimensional points.
+pub struct KdTree<const K: usize> {
+    nodes: Vec<Node<K>>,
+    root: Option<usize>,
+}
+
+fn dist_sq<const K: usize>(a: &[f64; K], b: &[f64; K]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+impl<const K: usize> KdTree<K> {
+    pub fn new(points: &[[f64; K]]) -> Self {
+        let mut pts = points.to_vec();
+        let mut nodes = Vec::with_capacity(pts.len());
+        let root = Self::build(&mut nodes, &mut pts, 0);
+        Self { nodes, root }
+    }
+
+    fn build(nodes: &mut Vec<Node<K>>, pts: &mut [[f64; K]], depth: usize) -> Option<usize> {
+        if pts.is_empty() || K == 0 {
+            return None;
+        }
+
+        let axis = depth % K;
+        let mid = pts.len() / 2;
+        pts.select_nth_unstable_by(mid, |a, b| a[axis].total_cmp(&b[axis]));
+
+        let index = nodes.len();
+        nodes.push(Node {
+            point: pts[mid],
+            axis,
+            left: None,
+            right: None,
+        });
+
+        let (lo, hi) = pts.split_at_mut(mid);
+        let left = Self::build(nodes, lo, depth + 1);
+        let right = Self::build(nodes, &mut hi[1..], depth + 1);
+        nodes[index].left = left;
+        nodes[index].right = right;
+
+        Some(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Closest stored point to `query` and its Euclidean distance.
+    pub fn nearest(&self, query: &[f64; K]) -> Option<([f64; K], f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        self.nearest_from(self.root, query, &mut best);
+        best.map(|(i, d)| (self.nodes[i].point, d.sqrt()))
+    }
+
+    fn nearest_from(&self, node: Option<usize>, query: &[f64; K], best: &mut Option<(usize, f64)>) {
+        let Some(index) = node else {
+            return;
+        };
+        let node = &self.nodes[index];
+
+        let d = dist_sq(&node.point, query);
+        if best.is_none_or(|(_, b)| d < b) {
+            *best = Some((index, d));
+        }
+
+        let diff = query[node.axis] - node.point[node.axis];
+        let (near, far) = if diff < 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+
+        self.nearest_from(near, query, best);
+        if best.is_none_or(|(_, b)| diff * diff < b) {
+            self.nearest_from(far, query, best);
+        }
+    }
+
+    /// Every stored point within distance `radius` of `query`.
+    pub fn within_radius(&self, query: &[f64; K], radius: f64) -> Vec<[f64; K]> {
+        let mut found = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        let r_sq = radius * radius;
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+
+            if dist_sq(&node.point, query) <= r_sq {
+                found.push(node.point);
+            }
+
+            let diff = query[node.axis] - node.point[node.axis];
+            if diff <= radius {
+                stack.extend(node.left);
+            }
+            if diff >= -radius {
+                stack.extend(node.right);
+            }
+        }
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next_f64(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn point<const K: usize>(&mut self) -> [f64; K] {
+            std::array::from_fn(|_| self.next_f64() * 100.0 - 50.0)
+        }
+    }
+
+    fn brute_nearest<const K: usize>(points: &[[f64; K]], q: &[f64; K]) -> f64 {
+        points
+            .iter()
+            .map(|p| dist_sq(p, q).sqrt())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn check_against_brute_force<const K: usize>(seed: u64) {
+        let mut rng = XorShift(seed);
+        let points: Vec<[f64; K]> = (0..500).map(|_| rng.point()).collect();
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.len(), points.len());
+
+        for _ in 0..200 {
+            let q = rng.point::<K>();
+            let (p, d) = tree.nearest(&q).unwrap();
+            assert_eq!(d, brute_nearest(&points, &q));
+            assert_eq!(d, dist_sq(&p, &q).sqrt());
+
+            let mut got = tree.within_radius(&q, 15.0);
+            let mut want: Vec<_> = points
+                .iter()
+                .copied()
+                .filter(|p| dist_sq(p, &q) <= 225.0)
+                .collect();
+            got.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            want.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(got, want);
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force_2d() {
+        check_against_brute_force::<2>(0x2545f4914f6cdd1d);
+    }
+
+    #[test]
+    fn test_matches_brute_force_3d() {
+        check_against_brute_force::<3>(0x9e3779b97f4a7c15);
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree: KdTree<2> = KdTree::new(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(&[0.0, 0.0]), None);
+        assert!(tree.within_radius(&[0.0, 0.0], 1.0).is_empty());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod geometry;
+pub mod kdtree;
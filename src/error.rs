@@ -0,0 +1,96 @@
+//! Crate-wide error type, so `?` composes across the matrix, io and number
+//! theory modules.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+pub use crate::math::matrix::utils::error::Error as MatrixError;
+
+#[derive(Debug)]
+pub enum UtilsError {
+    Matrix(MatrixError),
+    Io(io::Error),
+    Overflow,
+    DivisionByZero,
+}
+
+pub type Result<T> = std::result::Result<T, UtilsError>;
+
+impl fmt::Display for UtilsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UtilsError::Matrix(err) => write!(f, "{err}"),
+            UtilsError::Io(err) => write!(f, "I/O error: {err}"),
+            UtilsError::Overflow => write!(f, "Arithmetic overflow"),
+            UtilsError::DivisionByZero => write!(f, "Division by zero"),
+        }
+    }
+}
+
+impl StdError for UtilsError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            UtilsError::Matrix(err) => Some(err),
+            UtilsError::Io(err) => Some(err),
+            UtilsError::Overflow | UtilsError::DivisionByZero => None,
+        }
+    }
+}
+
+impl From<MatrixError> for UtilsError {
+    fn from(err: MatrixError) -> Self {
+        UtilsError::Matrix(err)
+    }
+}
+
+impl From<io::Error> for UtilsError {
+    fn from(err: io::Error) -> Self {
+        UtilsError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::matrix::Matrix;
+    use crate::math::matrix::ops::add;
+
+    fn matrix_step() -> Result<Matrix> {
+        Ok(add(&Matrix::zeros(2, 2), &Matrix::zeros(3, 3))?)
+    }
+
+    fn io_step() -> Result<std::fs::File> {
+        Ok(std::fs::File::open("/nonexistent/wg-utils/error-test")?)
+    }
+
+    #[test]
+    fn test_matrix_error_converts() {
+        let err = matrix_step().unwrap_err();
+
+        assert!(matches!(
+            err,
+            UtilsError::Matrix(MatrixError::MatrixSizeMismatch)
+        ));
+        assert!(err.source().is_some());
+        assert!(err.to_string().contains("Matrix size mismatch"));
+    }
+
+    #[test]
+    fn test_io_error_converts() {
+        let err = io_step().unwrap_err();
+
+        match &err {
+            UtilsError::Io(inner) => assert_eq!(inner.kind(), io::ErrorKind::NotFound),
+            other => panic!("expected an io error, got {other:?}"),
+        }
+        assert!(err.to_string().starts_with("I/O error"));
+    }
+
+    #[test]
+    fn test_arithmetic_errors() {
+        assert_eq!(UtilsError::Overflow.to_string(), "Arithmetic overflow");
+        assert_eq!(UtilsError::DivisionByZero.to_string(), "Division by zero");
+        assert!(UtilsError::DivisionByZero.source().is_none());
+    }
+}
@@ -0,0 +1,275 @@
+use crate::lfs::queue::BoundedLockFreeQueue;
+use crate::lfs::Backoff;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Creates a many-producer, single-consumer channel holding up to `capacity`
+/// messages (rounded up as by `BoundedLockFreeQueue::new`, which also panics
+/// on zero).
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        queue: BoundedLockFreeQueue::new(capacity),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+    });
+
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver {
+            shared,
+            _consumer: PhantomData,
+        },
+    )
+}
+
+struct Shared<T> {
+    queue: BoundedLockFreeQueue<T>,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The consuming end. `Send` but not `Sync`, so there is only ever one
+/// consumer.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    _consumer: PhantomData<Cell<()>>,
+}
+
+/// A message that could not be sent, handed back to the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendError<T> {
+    Full(T),
+    Disconnected(T),
+}
+
+impl<T> SendError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            SendError::Full(value) | SendError::Disconnected(value) => value,
+        }
+    }
+}
+
+/// Every sender was dropped and the channel is drained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Disconnected,
+}
+
+impl<T> Sender<T> {
+    /// Queues `value` without blocking.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if !self.shared.receiver_alive.load(Ordering::Acquire) {
+            return Err(SendError::Disconnected(value));
+        }
+        self.shared.queue.enqueue(value).map_err(SendError::Full)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Sender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.senders.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T> Receiver<T> {
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if let Some(value) = self.shared.queue.dequeue() {
+            return Ok(value);
+        }
+        if self.shared.senders.load(Ordering::Acquire) == 0 {
+            // a sender may have queued a message just before dropping
+            return self
+                .shared
+                .queue
+                .dequeue()
+                .ok_or(TryRecvError::Disconnected);
+        }
+        Err(TryRecvError::Empty)
+    }
+
+    /// Waits for a message, spinning briefly before yielding the thread.
+    /// Fails once every sender is gone and nothing is left to receive.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut backoff = Backoff::new();
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => backoff.snooze(),
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shared.queue.is_empty()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Full(_) => write!(f, "sending on a full channel"),
+            SendError::Disconnected(_) => write!(f, "sending on a disconnected channel"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for SendError<T> {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty and disconnected channel")
+    }
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receiving on an empty channel"),
+            TryRecvError::Disconnected => {
+                write!(f, "receiving on an empty and disconnected channel")
+            }
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_full_and_disconnected() {
+        let (tx, rx) = channel(2);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(tx.send(3), Err(SendError::Full(3)));
+
+        assert_eq!(rx.recv(), Ok(1));
+        tx.send(3).unwrap();
+        assert_eq!(rx.len(), 2);
+
+        drop(rx);
+        let err = tx.send(4).unwrap_err();
+        assert_eq!(err.to_string(), "sending on a disconnected channel");
+        assert_eq!(err.into_inner(), 4);
+    }
+
+    #[test]
+    fn test_dropping_senders_disconnects() {
+        let (tx, rx) = channel(4);
+        let tx2 = tx.clone();
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        tx.send("a").unwrap();
+        drop(tx);
+        tx2.send("b").unwrap();
+        drop(tx2);
+
+        // queued messages are still delivered after the last sender is gone
+        assert_eq!(rx.recv(), Ok("a"));
+        assert_eq!(rx.recv(), Ok("b"));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn test_many_senders_one_receiver() {
+        const SENDERS: usize = 4;
+        const PER_SENDER: usize = 25_000;
+
+        let (tx, rx) = channel(64);
+        let handles: Vec<_> = (0..SENDERS)
+            .map(|s| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..PER_SENDER {
+                        let mut msg = s * PER_SENDER + i;
+                        loop {
+                            match tx.send(msg) {
+                                Ok(()) => break,
+                                Err(SendError::Full(back)) => {
+                                    msg = back;
+                                    thread::yield_now();
+                                }
+                                Err(SendError::Disconnected(_)) => panic!("receiver dropped"),
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+
+        let mut received = Vec::new();
+        while let Ok(msg) = rx.recv() {
+            received.push(msg);
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // each sender's messages arrive in the order they were sent
+        let mut last = [None; SENDERS];
+        for &msg in &received {
+            let s = msg / PER_SENDER;
+            assert!(last[s] < Some(msg));
+            last[s] = Some(msg);
+        }
+
+        received.sort_unstable();
+        assert_eq!(received, (0..SENDERS * PER_SENDER).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,361 @@
+use std::cell::{Cell, UnsafeCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicIsize, AtomicPtr, Ordering};
+use std::sync::Arc;
+
+const INITIAL_CAPACITY: usize = 32;
+
+/// Result of a `steal` attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Steal<T> {
+    Success(T),
+    Empty,
+    /// Lost a race with the owner or another thief; the deque may still
+    /// hold items.
+    Retry,
+}
+
+/// Chase-Lev work-stealing deque.
+///
+/// The owning thread pushes and pops at the bottom (LIFO); any number of
+/// `Stealer` handles take from the top (FIFO). The deque itself is `Send` but
+/// not `Sync`, so only one thread can ever act as the owner.
+pub struct WorkStealingDeque<T> {
+    inner: Arc<Inner<T>>,
+    _owner: PhantomData<Cell<()>>,
+}
+
+/// A cloneable handle for stealing from a `WorkStealingDeque`.
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    top: AtomicIsize,
+    bottom: AtomicIsize,
+    buffer: AtomicPtr<Buffer<T>>,
+    // buffers replaced by growth; a thief may still be reading one, so they
+    // are only freed with the deque. Touched by the owner alone.
+    retired: UnsafeCell<Vec<*mut Buffer<T>>>,
+}
+
+struct Buffer<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Buffer<T> {
+    fn alloc(capacity: usize) -> *mut Self {
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        Box::into_raw(Box::new(Buffer { slots }))
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn slot(&self, index: isize) -> *mut MaybeUninit<T> {
+        self.slots[index as usize & (self.capacity() - 1)].get()
+    }
+
+    unsafe fn write(&self, index: isize, value: T) {
+        ptr::write(self.slot(index), MaybeUninit::new(value));
+    }
+
+    // a bitwise copy; only the caller that wins the slot may assume it is init
+    unsafe fn read(&self, index: isize) -> MaybeUninit<T> {
+        ptr::read_volatile(self.slot(index))
+    }
+}
+
+impl<T> WorkStealingDeque<T> {
+    pub fn new() -> Self {
+        WorkStealingDeque {
+            inner: Arc::new(Inner {
+                top: AtomicIsize::new(0),
+                bottom: AtomicIsize::new(0),
+                buffer: AtomicPtr::new(Buffer::alloc(INITIAL_CAPACITY)),
+                retired: UnsafeCell::new(Vec::new()),
+            }),
+            _owner: PhantomData,
+        }
+    }
+
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        let inner = &*self.inner;
+        let b = inner.bottom.load(Ordering::Relaxed);
+        let t = inner.top.load(Ordering::Acquire);
+        let mut buffer = inner.buffer.load(Ordering::Relaxed);
+
+        if b - t >= unsafe { (*buffer).capacity() } as isize {
+            buffer = unsafe { self.grow(buffer, t, b) };
+        }
+
+        unsafe { (*buffer).write(b, value) };
+        fence(Ordering::Release);
+        inner.bottom.store(b + 1, Ordering::Relaxed);
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let inner = &*self.inner;
+        let b = inner.bottom.load(Ordering::Relaxed) - 1;
+        let buffer = inner.buffer.load(Ordering::Relaxed);
+        inner.bottom.store(b, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let t = inner.top.load(Ordering::Relaxed);
+
+        if t > b {
+            // already empty
+            inner.bottom.store(b + 1, Ordering::Relaxed);
+            return None;
+        }
+
+        if t == b {
+            // last item: race the thieves for it
+            let won = inner
+                .top
+                .compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok();
+            inner.bottom.store(b + 1, Ordering::Relaxed);
+            if !won {
+                return None;
+            }
+        }
+
+        Some(unsafe { (*buffer).read(b).assume_init() })
+    }
+
+    pub fn steal(&self) -> Steal<T> {
+        self.inner.steal()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // copies the live items into a buffer twice the size
+    unsafe fn grow(&self, old: *mut Buffer<T>, t: isize, b: isize) -> *mut Buffer<T> {
+        let new = Buffer::alloc((*old).capacity() * 2);
+        for i in t..b {
+            ptr::copy_nonoverlapping((*old).slot(i), (*new).slot(i), 1);
+        }
+
+        self.inner.buffer.store(new, Ordering::Release);
+        (*self.inner.retired.get()).push(old);
+        new
+    }
+}
+
+impl<T> Default for WorkStealingDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Stealer<T> {
+    pub fn steal(&self) -> Steal<T> {
+        self.inner.steal()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Stealer {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Inner<T> {
+    fn steal(&self) -> Steal<T> {
+        let t = self.top.load(Ordering::Acquire);
+        fence(Ordering::SeqCst);
+        let b = self.bottom.load(Ordering::Acquire);
+
+        if t >= b {
+            return Steal::Empty;
+        }
+
+        let buffer = self.buffer.load(Ordering::Acquire);
+        let value = unsafe { (*buffer).read(t) };
+
+        if self
+            .top
+            .compare_exchange(t, t + 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            return Steal::Retry;
+        }
+
+        Steal::Success(unsafe { value.assume_init() })
+    }
+
+    fn len(&self) -> usize {
+        let b = self.bottom.load(Ordering::Acquire);
+        let t = self.top.load(Ordering::Acquire);
+        (b - t).max(0) as usize
+    }
+}
+
+impl<T> fmt::Debug for WorkStealingDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkStealingDeque")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Debug for Stealer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stealer")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let t = *self.top.get_mut();
+        let b = *self.bottom.get_mut();
+        let buffer = *self.buffer.get_mut();
+
+        unsafe {
+            for i in t..b {
+                (*buffer).read(i).assume_init_drop();
+            }
+            drop(Box::from_raw(buffer));
+            for old in self.retired.get_mut().drain(..) {
+                drop(Box::from_raw(old));
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+    use std::thread;
+
+    #[test]
+    fn test_owner_lifo_thief_fifo() {
+        let deque = WorkStealingDeque::new();
+        let stealer = deque.stealer();
+        assert_eq!(stealer.steal(), Steal::Empty);
+        assert_eq!(deque.pop(), None);
+
+        for i in 0..100 {
+            deque.push(i);
+        }
+        assert_eq!(deque.len(), 100);
+
+        assert_eq!(deque.pop(), Some(99));
+        assert_eq!(stealer.steal(), Steal::Success(0));
+        assert_eq!(stealer.clone().steal(), Steal::Success(1));
+        assert_eq!(deque.pop(), Some(98));
+        assert_eq!(format!("{deque:?}"), "WorkStealingDeque { len: 96, .. }");
+
+        while deque.pop().is_some() {}
+        assert!(deque.is_empty() && stealer.is_empty());
+    }
+
+    #[test]
+    fn test_drop_releases_remaining_items() {
+        let drops = Arc::new(AtomicUsize::new(0));
+
+        struct Counted(Arc<AtomicUsize>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let deque = WorkStealingDeque::new();
+        let stealer = deque.stealer();
+        // enough to grow the buffer a few times
+        for _ in 0..200 {
+            deque.push(Counted(Arc::clone(&drops)));
+        }
+        drop(deque.pop());
+        drop(stealer.steal());
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+
+        drop(deque);
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+        drop(stealer);
+        assert_eq!(drops.load(Ordering::Relaxed), 200);
+    }
+
+    #[test]
+    fn test_concurrent_steals_lose_and_duplicate_nothing() {
+        const ITEMS: usize = 100_000;
+        const THIEVES: usize = 4;
+
+        let deque = WorkStealingDeque::new();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let thieves: Vec<_> = (0..THIEVES)
+            .map(|_| {
+                let stealer = deque.stealer();
+                let done = Arc::clone(&done);
+                thread::spawn(move || {
+                    let mut stolen = Vec::new();
+                    loop {
+                        match stealer.steal() {
+                            Steal::Success(v) => stolen.push(v),
+                            Steal::Retry => {}
+                            Steal::Empty if done.load(Ordering::Acquire) => break,
+                            Steal::Empty => thread::yield_now(),
+                        }
+                    }
+                    stolen
+                })
+            })
+            .collect();
+
+        // interleave pushes with owner pops so both ends stay contended
+        let mut popped = Vec::new();
+        for i in 0..ITEMS {
+            deque.push(i);
+            if i % 3 == 0 {
+                popped.extend(deque.pop());
+            }
+        }
+        while let Some(v) = deque.pop() {
+            popped.push(v);
+        }
+        done.store(true, Ordering::Release);
+
+        let mut all = popped;
+        for thief in thieves {
+            all.extend(thief.join().unwrap());
+        }
+        all.sort_unstable();
+        assert_eq!(all, (0..ITEMS).collect::<Vec<_>>());
+    }
+}
@@ -1,2 +1,67 @@
+pub mod channel;
+pub mod deque;
 pub mod queue;
 pub mod stack;
+
+use std::thread;
+
+const SPIN_LIMIT: u32 = 6;
+
+/// Exponential backoff for retry loops.
+///
+/// Each `snooze` spins twice as long as the last, up to `2^(SPIN_LIMIT - 1)`
+/// spins, after which it yields the thread instead.
+#[derive(Debug, Default)]
+pub struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    pub const fn new() -> Self {
+        Backoff { step: 0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+
+    pub fn snooze(&mut self) {
+        if self.step < SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                core::hint::spin_loop();
+            }
+            self.step += 1;
+        } else {
+            thread::yield_now();
+        }
+    }
+
+    /// Whether `snooze` has escalated to yielding the thread.
+    pub fn is_yielding(&self) -> bool {
+        self.step >= SPIN_LIMIT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_escalates_then_resets() {
+        let mut backoff = Backoff::new();
+        for step in 0..SPIN_LIMIT {
+            assert_eq!(backoff.step, step);
+            assert!(!backoff.is_yielding());
+            backoff.snooze();
+        }
+        assert!(backoff.is_yielding());
+
+        // yielding is the ceiling
+        backoff.snooze();
+        assert_eq!(backoff.step, SPIN_LIMIT);
+
+        backoff.reset();
+        assert!(!backoff.is_yielding());
+        assert_eq!(backoff.step, Backoff::default().step);
+    }
+}
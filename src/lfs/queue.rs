@@ -1,6 +1,8 @@
+use crate::lfs::Backoff;
 use std::cell::UnsafeCell;
+use std::fmt;
 use std::ptr;
-use std::sync::atomic::{fence, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 
 #[allow(dead_code)]
 pub struct LockFreeQueue<T> {
@@ -30,12 +32,25 @@ impl<T> LockFreeQueue<T> {
 
     #[inline(always)]
     pub fn enqueue(&self, value: T) {
+        // usize::MAX attempts never runs out in practice
+        let _ = self.try_enqueue_n(value, usize::MAX);
+    }
+
+    #[inline(always)]
+    pub fn dequeue(&self) -> Option<T> {
+        self.try_dequeue_n(usize::MAX)
+    }
+
+    /// Like `enqueue`, but gives `value` back after `max_attempts` failed
+    /// attempts, backing off between them.
+    pub fn try_enqueue_n(&self, value: T, max_attempts: usize) -> Result<(), T> {
         let new_node = Box::into_raw(Box::new(Node {
             value: UnsafeCell::new(Some(value)),
             next: AtomicPtr::new(ptr::null_mut()),
         }));
 
-        loop {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
             let tail = self.tail.load(Ordering::Acquire);
             let tail_next = unsafe { (*tail).next.load(Ordering::Acquire) };
 
@@ -57,7 +72,7 @@ impl<T> LockFreeQueue<T> {
                             Ordering::Release,
                             Ordering::Relaxed,
                         );
-                        return;
+                        return Ok(());
                     }
                 } else {
                     let _ = self.tail.compare_exchange_weak(
@@ -68,13 +83,18 @@ impl<T> LockFreeQueue<T> {
                     );
                 }
             }
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+
+        let node = unsafe { Box::from_raw(new_node) };
+        Err(node.value.into_inner().unwrap())
     }
 
-    #[inline(always)]
-    pub fn dequeue(&self) -> Option<T> {
-        loop {
+    /// Like `dequeue`, but returns `None` after `max_attempts` failed
+    /// attempts, backing off between them.
+    pub fn try_dequeue_n(&self, max_attempts: usize) -> Option<T> {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
             let head = self.head.load(Ordering::Acquire);
             let tail = self.tail.load(Ordering::Acquire);
             let head_next = unsafe { (*head).next.load(Ordering::Acquire) };
@@ -101,7 +121,9 @@ impl<T> LockFreeQueue<T> {
                         )
                         .is_ok()
                     {
-                        let value = unsafe { ptr::read(&(*head_next).value) }.into_inner();
+                        // take rather than copy: `head_next` becomes the new
+                        // sentinel and would drop the value again when freed
+                        let value = unsafe { (*(*head_next).value.get()).take() };
 
                         unsafe { drop(Box::from_raw(head)) };
 
@@ -109,8 +131,9 @@ impl<T> LockFreeQueue<T> {
                     }
                 }
             }
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+        None
     }
 
     #[inline(always)]
@@ -121,6 +144,14 @@ impl<T> LockFreeQueue<T> {
     }
 }
 
+impl<T> fmt::Debug for LockFreeQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockFreeQueue")
+            .field("is_empty", &self.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
 impl<T> Drop for LockFreeQueue<T> {
     fn drop(&mut self) {
         while self.dequeue().is_some() {}
@@ -132,22 +163,39 @@ impl<T> Drop for LockFreeQueue<T> {
 
 #[allow(dead_code)]
 pub struct BoundedLockFreeQueue<T> {
-    buffer: *mut Node<T>,
+    buffer: *mut Slot<T>,
     capacity: usize,
+    requested_capacity: usize,
     head: AtomicUsize,
     tail: AtomicUsize,
     cache_line_pad: [u8; 64],
 }
 
+// `sequence` equals the position a slot is ready to be written at, and that
+// position + 1 once the value is published for the matching dequeue.
+struct Slot<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<Option<T>>,
+}
+
 impl<T> BoundedLockFreeQueue<T> {
+    /// Creates a queue holding at least `capacity` items. The buffer is
+    /// rounded up to the next power of two, so `capacity()` may be larger than
+    /// requested; `requested_capacity()` returns the original value.
+    ///
+    /// Panics if `capacity` is zero.
     pub fn new(capacity: usize) -> Self {
-        let capacity = capacity.next_power_of_two();
+        assert!(capacity > 0, "Capacity must be positive");
+        let requested_capacity = capacity;
+        let capacity = capacity
+            .checked_next_power_of_two()
+            .expect("Capacity overflows the next power of two");
 
         let mut buffer = Vec::with_capacity(capacity);
-        for _ in 0..capacity {
-            buffer.push(Node {
+        for i in 0..capacity {
+            buffer.push(Slot {
+                sequence: AtomicUsize::new(i),
                 value: UnsafeCell::new(None),
-                next: AtomicPtr::new(ptr::null_mut()),
             });
         }
 
@@ -156,6 +204,7 @@ impl<T> BoundedLockFreeQueue<T> {
         BoundedLockFreeQueue {
             buffer,
             capacity,
+            requested_capacity,
             head: AtomicUsize::new(0),
             tail: AtomicUsize::new(0),
             cache_line_pad: [0; 64],
@@ -164,66 +213,86 @@ impl<T> BoundedLockFreeQueue<T> {
 
     #[inline(always)]
     pub fn enqueue(&self, value: T) -> Result<(), T> {
+        self.try_enqueue_n(value, usize::MAX)
+    }
+
+    #[inline(always)]
+    pub fn dequeue(&self) -> Option<T> {
+        self.try_dequeue_n(usize::MAX)
+    }
+
+    /// Like `enqueue`, but also gives `value` back after `max_attempts` lost
+    /// races, backing off between them.
+    pub fn try_enqueue_n(&self, value: T, max_attempts: usize) -> Result<(), T> {
         let mask = self.capacity - 1;
         let mut tail = self.tail.load(Ordering::Relaxed);
 
-        loop {
-            let head = self.head.load(Ordering::Acquire);
-
-            if (tail - head) >= self.capacity {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
+            let slot = unsafe { &*self.buffer.add(tail & mask) };
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence as isize - tail as isize;
+
+            if diff == 0 {
+                match self.tail.compare_exchange_weak(
+                    tail,
+                    tail + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { *slot.value.get() = Some(value) };
+                        slot.sequence.store(tail + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => tail = current,
+                }
+            } else if diff < 0 {
+                // the slot still holds the value from one lap ago
                 return Err(value);
+            } else {
+                tail = self.tail.load(Ordering::Relaxed);
             }
-
-            if self
-                .tail
-                .compare_exchange_weak(tail, tail + 1, Ordering::Relaxed, Ordering::Relaxed)
-                .is_ok()
-            {
-                let index = tail & mask;
-                let node = unsafe { &*self.buffer.add(index) };
-
-                unsafe { *node.value.get() = Some(value) };
-
-                fence(Ordering::Release);
-
-                return Ok(());
-            }
-
-            tail = self.tail.load(Ordering::Relaxed);
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+        Err(value)
     }
 
-    #[inline(always)]
-    pub fn dequeue(&self) -> Option<T> {
+    /// Like `dequeue`, but returns `None` after `max_attempts` lost races,
+    /// backing off between them.
+    pub fn try_dequeue_n(&self, max_attempts: usize) -> Option<T> {
         let mask = self.capacity - 1;
         let mut head = self.head.load(Ordering::Relaxed);
 
-        loop {
-            let tail = self.tail.load(Ordering::Acquire);
-
-            if head >= tail {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
+            let slot = unsafe { &*self.buffer.add(head & mask) };
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            let diff = sequence as isize - (head + 1) as isize;
+
+            if diff == 0 {
+                match self.head.compare_exchange_weak(
+                    head,
+                    head + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let value = unsafe { (*slot.value.get()).take() };
+                        slot.sequence.store(head + self.capacity, Ordering::Release);
+                        return value;
+                    }
+                    Err(current) => head = current,
+                }
+            } else if diff < 0 {
+                // nothing published at this position yet
                 return None;
+            } else {
+                head = self.head.load(Ordering::Relaxed);
             }
-
-            if self
-                .head
-                .compare_exchange_weak(head, head + 1, Ordering::Relaxed, Ordering::Relaxed)
-                .is_ok()
-            {
-                let index = head & mask;
-                let node = unsafe { &*self.buffer.add(index) };
-
-                fence(Ordering::Acquire);
-
-                let value = unsafe { (*node.value.get()).take() };
-
-                return value;
-            }
-
-            head = self.head.load(Ordering::Relaxed);
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+        None
     }
 
     #[inline(always)]
@@ -245,10 +314,45 @@ impl<T> BoundedLockFreeQueue<T> {
         tail.saturating_sub(head)
     }
 
+    /// The number of slots actually allocated, a power of two.
     #[inline(always)]
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+
+    #[inline(always)]
+    pub fn requested_capacity(&self) -> usize {
+        self.requested_capacity
+    }
+}
+
+impl<T: Clone> BoundedLockFreeQueue<T> {
+    /// Clones the queued items, front first, without removing them.
+    ///
+    /// Takes `&mut self` because a concurrent `dequeue` moves a value out of
+    /// its slot while it would be being cloned; with exclusive access the
+    /// result is exact rather than a racy best guess.
+    pub fn snapshot(&mut self) -> Vec<T> {
+        let mask = self.capacity - 1;
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+
+        (head..tail)
+            .map(|pos| {
+                let slot = unsafe { &*self.buffer.add(pos & mask) };
+                unsafe { (*slot.value.get()).clone() }.expect("published slot holds a value")
+            })
+            .collect()
+    }
+}
+
+impl<T> fmt::Debug for BoundedLockFreeQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedLockFreeQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
 }
 
 unsafe impl<T: Send> Send for BoundedLockFreeQueue<T> {}
@@ -256,13 +360,302 @@ unsafe impl<T: Send> Sync for BoundedLockFreeQueue<T> {}
 
 impl<T> Drop for BoundedLockFreeQueue<T> {
     fn drop(&mut self) {
-        while self.dequeue().is_some() {}
-
+        // every slot is initialized, and a dequeued slot holds `None`, so
+        // dropping the buffer drops each remaining item exactly once
         unsafe {
-            Vec::from_raw_parts(self.buffer, self.capacity, self.capacity);
+            drop(Vec::from_raw_parts(
+                self.buffer,
+                self.capacity,
+                self.capacity,
+            ));
         }
     }
 }
 
 unsafe impl<T: Send> Send for LockFreeQueue<T> {}
 unsafe impl<T: Send> Sync for LockFreeQueue<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        let queue = LockFreeQueue::new();
+        queue.enqueue(1);
+        assert_eq!(
+            format!("{queue:?}"),
+            "LockFreeQueue { is_empty: false, .. }"
+        );
+
+        let bounded = BoundedLockFreeQueue::new(4);
+        bounded.enqueue(1).unwrap();
+        bounded.enqueue(2).unwrap();
+        assert_eq!(
+            format!("{bounded:?}"),
+            "BoundedLockFreeQueue { len: 2, capacity: 4, .. }"
+        );
+    }
+
+    #[test]
+    fn test_bounded_capacity_rounding() {
+        let queue = BoundedLockFreeQueue::new(5);
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.requested_capacity(), 5);
+
+        // the rounded-up capacity is what bounds the queue
+        for i in 0..8 {
+            queue.enqueue(i).unwrap();
+        }
+        assert!(queue.is_full());
+        assert_eq!(queue.enqueue(8), Err(8));
+
+        let exact = BoundedLockFreeQueue::<u8>::new(1);
+        assert_eq!((exact.capacity(), exact.requested_capacity()), (1, 1));
+    }
+
+    #[test]
+    fn test_bounded_snapshot_keeps_items() {
+        let mut queue = BoundedLockFreeQueue::new(4);
+        assert!(queue.snapshot().is_empty());
+
+        // wrap around the ring so the live range spans its end
+        for i in 0..3 {
+            queue.enqueue(i.to_string()).unwrap();
+        }
+        queue.dequeue();
+        queue.dequeue();
+        for i in 3..6 {
+            queue.enqueue(i.to_string()).unwrap();
+        }
+
+        assert_eq!(queue.snapshot(), ["2", "3", "4", "5"]);
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.dequeue().as_deref(), Some("2"));
+        assert_eq!(queue.snapshot(), ["3", "4", "5"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be positive")]
+    fn test_bounded_zero_capacity() {
+        BoundedLockFreeQueue::<u8>::new(0);
+    }
+
+    #[test]
+    fn test_bounded_concurrent_no_loss() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 20_000;
+
+        let queue = Arc::new(BoundedLockFreeQueue::new(16));
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let mut value = p * PER_PRODUCER + i;
+                        while let Err(back) = queue.enqueue(value) {
+                            value = back;
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut taken = Vec::new();
+                    while taken.len() < PRODUCERS * PER_PRODUCER / 2 {
+                        match queue.dequeue() {
+                            Some(v) => taken.push(v),
+                            None => thread::yield_now(),
+                        }
+                    }
+                    taken
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let mut all: Vec<usize> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..PRODUCERS * PER_PRODUCER).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_bounded_consumers_never_see_unwritten_slots() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+        use std::thread;
+
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 20_000;
+
+        // a large payload keeps producers inside the slot write for a while,
+        // so a consumer that claims a slot before the write finishes gets a
+        // `None` (losing the item) or a torn value rather than a hang
+        let queue = Arc::new(BoundedLockFreeQueue::<[usize; 512]>::new(1024));
+        let done = Arc::new(AtomicBool::new(false));
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let done = Arc::clone(&done);
+                thread::spawn(move || {
+                    let mut taken = Vec::new();
+                    while !done.load(Ordering::Acquire) {
+                        match queue.dequeue() {
+                            // a torn value reads as an id no producer sent
+                            Some(value) => taken.push(if value.iter().all(|&x| x == value[0]) {
+                                value[0]
+                            } else {
+                                usize::MAX
+                            }),
+                            None => thread::yield_now(),
+                        }
+                    }
+                    taken
+                })
+            })
+            .collect();
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let mut value = [p * PER_PRODUCER + i; 512];
+                        while let Err(back) = queue.enqueue(value) {
+                            value = back;
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        done.store(true, Ordering::Release);
+        let mut all: Vec<usize> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        all.extend(std::iter::from_fn(|| queue.dequeue()).map(|value| value[0]));
+        all.sort_unstable();
+        assert_eq!(all, (0..PRODUCERS * PER_PRODUCER).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_items_dropped_exactly_once() {
+        use std::sync::Arc;
+
+        struct Counted(Arc<AtomicUsize>);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let bounded = BoundedLockFreeQueue::new(8);
+        // wrap around once so dequeued and live slots are interleaved
+        for _ in 0..6 {
+            assert!(bounded.enqueue(Counted(Arc::clone(&drops))).is_ok());
+        }
+        for _ in 0..5 {
+            drop(bounded.dequeue());
+        }
+        for _ in 0..4 {
+            assert!(bounded.enqueue(Counted(Arc::clone(&drops))).is_ok());
+        }
+        assert_eq!(drops.load(Ordering::Relaxed), 5);
+        drop(bounded);
+        assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+        drops.store(0, Ordering::Relaxed);
+        let queue = LockFreeQueue::new();
+        for _ in 0..6 {
+            queue.enqueue(Counted(Arc::clone(&drops)));
+        }
+        for _ in 0..3 {
+            drop(queue.dequeue());
+        }
+        assert_eq!(drops.load(Ordering::Relaxed), 3);
+        drop(queue);
+        assert_eq!(drops.load(Ordering::Relaxed), 6);
+        assert_eq!(Arc::strong_count(&drops), 1);
+    }
+
+    #[test]
+    fn test_try_ops_give_up_under_contention() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let queue = Arc::new(LockFreeQueue::new());
+        let bounded = Arc::new(BoundedLockFreeQueue::new(64));
+        // a zero budget never touches the queue
+        assert_eq!(queue.try_enqueue_n(1, 0), Err(1));
+        assert_eq!(bounded.try_enqueue_n(1, 0), Err(1));
+        queue.enqueue(1);
+        bounded.enqueue(1).unwrap();
+        assert_eq!(
+            (queue.try_dequeue_n(0), bounded.try_dequeue_n(0)),
+            (None, None)
+        );
+        assert_eq!(queue.try_dequeue_n(1), Some(1));
+        assert_eq!(bounded.try_dequeue_n(1), Some(1));
+
+        // `LockFreeQueue` frees nodes on dequeue without a reclamation
+        // scheme, so only its enqueue side is contended here
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let queue = Arc::clone(&queue);
+                let bounded = Arc::clone(&bounded);
+                thread::spawn(move || {
+                    let mut counts = [0usize; 3];
+                    for i in 0..20_000 {
+                        let value = t * 20_000 + i;
+                        match queue.try_enqueue_n(value, 2) {
+                            Ok(()) => counts[0] += 1,
+                            Err(back) => assert_eq!(back, value),
+                        }
+                        match bounded.try_enqueue_n(value, 2) {
+                            Ok(()) => counts[1] += 1,
+                            Err(back) => assert_eq!(back, value),
+                        }
+                        counts[2] += bounded.try_dequeue_n(2).is_some() as usize;
+                    }
+                    counts
+                })
+            })
+            .collect();
+
+        let mut totals = [0usize; 3];
+        for handle in handles {
+            for (total, count) in totals.iter_mut().zip(handle.join().unwrap()) {
+                *total += count;
+            }
+        }
+        let mut left = [0usize; 2];
+        while queue.dequeue().is_some() {
+            left[0] += 1;
+        }
+        while bounded.dequeue().is_some() {
+            left[1] += 1;
+        }
+        assert_eq!(totals[0], left[0]);
+        assert_eq!(totals[1], totals[2] + left[1]);
+    }
+}
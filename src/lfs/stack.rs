@@ -1,3 +1,5 @@
+use crate::lfs::Backoff;
+use std::fmt;
 use std::ptr;
 use std::sync::atomic::{AtomicPtr, Ordering};
 
@@ -19,12 +21,25 @@ impl<T> LockFreeStack<T> {
 
     #[inline(always)]
     pub fn push(&self, value: T) {
+        // usize::MAX attempts never runs out in practice
+        let _ = self.try_push_n(value, usize::MAX);
+    }
+
+    #[inline(always)]
+    pub fn pop(&self) -> Option<T> {
+        self.try_pop_n(usize::MAX)
+    }
+
+    /// Like `push`, but gives `value` back after `max_attempts` failed CAS
+    /// attempts, backing off between them.
+    pub fn try_push_n(&self, value: T, max_attempts: usize) -> Result<(), T> {
         let new_node = Box::into_raw(Box::new(Node {
             value,
             next: ptr::null_mut(),
         }));
 
-        loop {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
             let top = self.top.load(Ordering::Acquire);
             unsafe { (*new_node).next = top };
 
@@ -33,16 +48,20 @@ impl<T> LockFreeStack<T> {
                 .compare_exchange_weak(top, new_node, Ordering::Release, Ordering::Relaxed)
                 .is_ok()
             {
-                break;
+                return Ok(());
             }
 
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+
+        Err(unsafe { Box::from_raw(new_node) }.value)
     }
 
-    #[inline(always)]
-    pub fn pop(&self) -> Option<T> {
-        loop {
+    /// Like `pop`, but returns `None` after `max_attempts` failed CAS
+    /// attempts, backing off between them.
+    pub fn try_pop_n(&self, max_attempts: usize) -> Option<T> {
+        let mut backoff = Backoff::new();
+        for _ in 0..max_attempts {
             let top = self.top.load(Ordering::Acquire);
             if top.is_null() {
                 return None;
@@ -55,13 +74,45 @@ impl<T> LockFreeStack<T> {
                 .compare_exchange_weak(top, next, Ordering::Release, Ordering::Relaxed)
                 .is_ok()
             {
-                let value = unsafe { ptr::read(&(*top).value) };
-                unsafe { drop(Box::from_raw(top)) };
-                return Some(value);
+                // move the value out of the box so it isn't dropped twice
+                let node = unsafe { Box::from_raw(top) };
+                return Some(node.value);
             }
 
-            core::hint::spin_loop();
+            backoff.snooze();
         }
+        None
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.top.load(Ordering::Acquire).is_null()
+    }
+}
+
+impl<T> fmt::Debug for LockFreeStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockFreeStack")
+            .field("is_empty", &self.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> LockFreeStack<T> {
+    /// Clones the stacked items, top first, without removing them.
+    ///
+    /// Takes `&mut self` because a concurrent `pop` frees nodes the walk may
+    /// still be reading; there is no reclamation scheme to keep them alive.
+    pub fn snapshot(&mut self) -> Vec<T> {
+        let mut values = Vec::new();
+        let mut node = *self.top.get_mut();
+        while !node.is_null() {
+            unsafe {
+                values.push((*node).value.clone());
+                node = (*node).next;
+            }
+        }
+        values
     }
 }
 
@@ -70,3 +121,79 @@ impl<T> Drop for LockFreeStack<T> {
         while self.pop().is_some() {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_keeps_items() {
+        let mut stack = LockFreeStack::new();
+        assert!(stack.snapshot().is_empty());
+        for i in 0..5 {
+            stack.push(i.to_string());
+        }
+
+        assert_eq!(stack.snapshot(), ["4", "3", "2", "1", "0"]);
+        assert_eq!(stack.pop().as_deref(), Some("4"));
+        stack.push("extra".to_string());
+        assert_eq!(stack.snapshot(), ["extra", "3", "2", "1", "0"]);
+    }
+
+    #[test]
+    fn test_debug() {
+        let stack = LockFreeStack::new();
+        assert_eq!(format!("{stack:?}"), "LockFreeStack { is_empty: true, .. }");
+
+        stack.push(1);
+        assert_eq!(
+            format!("{stack:?}"),
+            "LockFreeStack { is_empty: false, .. }"
+        );
+        assert_eq!(stack.pop(), Some(1));
+    }
+
+    #[test]
+    fn test_try_ops_give_up_under_contention() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let stack = Arc::new(LockFreeStack::new());
+        // a zero budget never touches the stack
+        assert_eq!(stack.try_push_n(1, 0), Err(1));
+        stack.push(1);
+        assert_eq!(stack.try_pop_n(0), None);
+        assert_eq!(stack.try_pop_n(1), Some(1));
+
+        // pop frees nodes without a reclamation scheme, so there is a single
+        // popper racing the pushers
+        let pushers: Vec<_> = (0..3)
+            .map(|t| {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || {
+                    let mut pushed = 0usize;
+                    for i in 0..20_000 {
+                        let value = t * 20_000 + i;
+                        match stack.try_push_n(value, 2) {
+                            Ok(()) => pushed += 1,
+                            Err(back) => assert_eq!(back, value),
+                        }
+                    }
+                    pushed
+                })
+            })
+            .collect();
+        let popper = {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || (0..60_000).filter(|_| stack.try_pop_n(2).is_some()).count())
+        };
+
+        let pushed: usize = pushers.into_iter().map(|h| h.join().unwrap()).sum();
+        let popped = popper.join().unwrap();
+        let mut left = 0;
+        while stack.pop().is_some() {
+            left += 1;
+        }
+        assert_eq!(pushed, popped + left);
+    }
+}
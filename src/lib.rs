@@ -1,3 +1,5 @@
+pub mod algo;
+pub mod error;
 pub mod lfs;
 pub mod math;
 pub mod r#unsafe;
@@ -1,6 +1,5 @@
-mod math;
-use math::fib;
 use std::io;
+use wg_utils::math::fib;
 
 fn main() {
     let mut input = String::new();
@@ -0,0 +1,106 @@
+//! Cheap approximations of common transcendental functions.
+//!
+//! Each function states its worst-case relative error over its valid domain;
+//! the tests in this module check those bounds against `std`.
+
+use std::f64::consts::{LN_2, LOG2_E, SQRT_2};
+
+/// `1 / sqrt(x)` via the Quake III bit hack plus one Newton-Raphson step.
+///
+/// Relative error below 0.2% for all positive normal `x`.
+#[inline]
+pub fn fast_inv_sqrt(x: f32) -> f32 {
+    let i = 0x5f3759df - (x.to_bits() >> 1);
+    let y = f32::from_bits(i);
+    y * (1.5 - 0.5 * x * y * y)
+}
+
+/// `e^x` by splitting `x * log2(e)` into an integer power of two, built
+/// directly in the exponent bits, and a fractional part evaluated with a
+/// degree-6 Taylor polynomial.
+///
+/// Relative error below 1e-6 for `x` in [-87, 88]; inputs outside that range
+/// saturate to the nearest representable normal result.
+#[inline]
+pub fn fast_exp(x: f32) -> f32 {
+    let x = x.clamp(-87.0, 88.0) as f64;
+    let n = (x * LOG2_E).round();
+    let r = x - n * LN_2; // |r| <= ln(2) / 2
+
+    let p = 1.0
+        + r * (1.0
+            + r * (0.5 + r * (1.0 / 6.0 + r * (1.0 / 24.0 + r * (1.0 / 120.0 + r / 720.0)))));
+    let scale = f32::from_bits(((n as i32 + 127) as u32) << 23);
+
+    p as f32 * scale
+}
+
+/// Natural log by splitting `x` into mantissa and exponent, then evaluating
+/// `ln(m) = 2 atanh((m - 1) / (m + 1))` with a four-term odd series.
+///
+/// Relative error below 1e-6 for positive normal `x`; returns NaN for
+/// `x <= 0`.
+#[inline]
+pub fn fast_log(x: f32) -> f32 {
+    if x.is_nan() || x <= 0.0 {
+        return f32::NAN;
+    }
+
+    let bits = x.to_bits();
+    let mut e = ((bits >> 23) & 0xff) as i32 - 127;
+    let mut m = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000) as f64;
+
+    // keep m in [sqrt(1/2), sqrt(2)) so ln(m) and e * ln(2) never cancel
+    if m > SQRT_2 {
+        m /= 2.0;
+        e += 1;
+    }
+
+    let t = (m - 1.0) / (m + 1.0);
+    let t2 = t * t;
+    let ln_m = 2.0 * t * (1.0 + t2 * (1.0 / 3.0 + t2 * (1.0 / 5.0 + t2 * (1.0 / 7.0))));
+
+    (e as f64 * LN_2 + ln_m) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel_err(got: f32, want: f32) -> f32 {
+        ((got - want) / want).abs()
+    }
+
+    fn inputs(lo: f32, hi: f32, n: usize) -> impl Iterator<Item = f32> {
+        (0..=n).map(move |i| lo + (hi - lo) * i as f32 / n as f32)
+    }
+
+    #[test]
+    fn test_fast_inv_sqrt() {
+        for x in inputs(1e-3, 1e4, 100_000) {
+            assert!(rel_err(fast_inv_sqrt(x), 1.0 / x.sqrt()) < 2e-3, "x = {x}");
+        }
+    }
+
+    #[test]
+    fn test_fast_exp() {
+        for x in inputs(-87.0, 88.0, 100_000) {
+            assert!(rel_err(fast_exp(x), x.exp()) < 1e-6, "x = {x}");
+        }
+        assert_eq!(fast_exp(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_fast_log() {
+        for x in inputs(1e-3, 1e4, 100_000).chain(inputs(0.9, 1.1, 10_000)) {
+            let want = x.ln();
+            if want == 0.0 {
+                assert_eq!(fast_log(x), 0.0);
+            } else {
+                assert!(rel_err(fast_log(x), want) < 1e-6, "x = {x}");
+            }
+        }
+        assert!(fast_log(0.0).is_nan());
+        assert!(fast_log(-1.0).is_nan());
+    }
+}
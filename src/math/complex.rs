@@ -0,0 +1,175 @@
+use num_traits::{Float, Num};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A complex number `re + im * i`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Complex<T = f64> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+impl<T: Num + Copy> Complex<T> {
+    pub fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(T::one(), T::zero())
+    }
+
+    pub fn i() -> Self {
+        Self::new(T::zero(), T::one())
+    }
+
+    pub fn from_real(re: T) -> Self {
+        Self::new(re, T::zero())
+    }
+
+    /// `|z|^2`, which avoids the square root of `norm`.
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn scale(&self, k: T) -> Self {
+        Self::new(self.re * k, self.im * k)
+    }
+}
+
+impl<T: Num + Copy + Neg<Output = T>> Complex<T> {
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl<T: Float> Complex<T> {
+    /// `r * e^(i theta)`.
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    pub fn norm(&self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    pub fn arg(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    pub fn exp(&self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+}
+
+impl<T: Num + Copy> Add for Complex<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Num + Copy> Sub for Complex<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Num + Copy> Mul for Complex<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: Num + Copy> Div for Complex<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.norm_sqr();
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
+
+impl<T: Num + Copy + Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Num + Copy> AddAssign for Complex<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Num + Copy> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Num + Copy> MulAssign for Complex<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: fmt::Display + PartialOrd + Num + Copy + Neg<Output = T>> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im < T::zero() {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!((a * b) / b, a);
+        assert_eq!(Complex::<f64>::i() * Complex::i(), -Complex::one());
+        assert_eq!(a.conj(), Complex::new(1.0, -2.0));
+        assert_eq!(a * a.conj(), Complex::from_real(a.norm_sqr()));
+    }
+
+    #[test]
+    fn test_polar() {
+        let z = Complex::from_polar(2.0, PI / 3.0);
+
+        assert!((z.norm() - 2.0).abs() < 1e-12);
+        assert!((z.arg() - PI / 3.0).abs() < 1e-12);
+
+        let e = Complex::new(0.0, PI).exp();
+        assert!((e.re + 1.0).abs() < 1e-12 && e.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Complex::new(1.5, -2.0).to_string(), "1.5-2i");
+        assert_eq!(Complex::new(0, 3).to_string(), "0+3i");
+    }
+}
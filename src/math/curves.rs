@@ -0,0 +1,99 @@
+/// Point on the Bezier curve defined by `control_points` at `t` in [0, 1],
+/// evaluated with de Casteljau's algorithm.
+pub fn bezier(control_points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    assert!(
+        !control_points.is_empty(),
+        "Bezier curve requires at least one control point"
+    );
+
+    let mut pts = control_points.to_vec();
+    for level in (1..pts.len()).rev() {
+        for i in 0..level {
+            pts[i] = lerp(pts[i], pts[i + 1], t);
+        }
+    }
+    pts[0]
+}
+
+/// Point on a uniform Catmull-Rom spline at `t` in [0, 1].
+///
+/// The spline passes through `points[1..n - 1]`; the first and last points
+/// only shape the end tangents. `t` is spread evenly over the `n - 3`
+/// segments, so `points[k]` is reached at `t = (k - 1) / (n - 3)`.
+pub fn catmull_rom(points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    assert!(
+        points.len() >= 4,
+        "Catmull-Rom spline requires at least four points"
+    );
+
+    let segments = points.len() - 3;
+    let scaled = t.clamp(0.0, 1.0) * segments as f64;
+    let seg = (scaled.floor() as usize).min(segments - 1);
+    let u = scaled - seg as f64;
+
+    let [p0, p1, p2, p3] = [
+        points[seg],
+        points[seg + 1],
+        points[seg + 2],
+        points[seg + 3],
+    ];
+    let u2 = u * u;
+    let u3 = u2 * u;
+
+    let eval = |a: f64, b: f64, c: f64, d: f64| {
+        0.5 * (2.0 * b
+            + (-a + c) * u
+            + (2.0 * a - 5.0 * b + 4.0 * c - d) * u2
+            + (-a + 3.0 * b - 3.0 * c + d) * u3)
+    };
+
+    (eval(p0.0, p1.0, p2.0, p3.0), eval(p0.1, p1.1, p2.1, p3.1))
+}
+
+fn lerp(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_cubic_bezier_endpoints() {
+        let ctrl = [(0.0, 0.0), (1.0, 3.0), (4.0, -2.0), (5.0, 1.0)];
+
+        assert!(approx(bezier(&ctrl, 0.0), (0.0, 0.0)));
+        assert!(approx(bezier(&ctrl, 1.0), (5.0, 1.0)));
+    }
+
+    #[test]
+    fn test_bezier_midpoint() {
+        let quad = [(0.0, 0.0), (1.0, 2.0), (2.0, 0.0)];
+        assert!(approx(bezier(&quad, 0.5), (1.0, 1.0)));
+
+        let line = [(0.0, 0.0), (4.0, 8.0)];
+        assert!(approx(bezier(&line, 0.25), (1.0, 2.0)));
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_interior_points() {
+        let pts = [
+            (0.0, 0.0),
+            (1.0, 2.0),
+            (3.0, 3.0),
+            (4.0, 1.0),
+            (6.0, 0.0),
+            (7.0, 2.0),
+        ];
+        let segments = (pts.len() - 3) as f64;
+
+        for (k, &p) in pts.iter().enumerate().take(pts.len() - 1).skip(1) {
+            let t = (k - 1) as f64 / segments;
+            assert!(approx(catmull_rom(&pts, t), p), "missed point {k}");
+        }
+    }
+}
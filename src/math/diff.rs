@@ -0,0 +1,98 @@
+//! Numeric derivatives by central differences.
+
+use crate::math::matrix::matrix::Matrix;
+
+/// Gradient of `f` at `x`: `(f(x + h e_i) - f(x - h e_i)) / 2h` per component.
+pub fn gradient<F>(f: F, x: &[f64], h: f64) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    assert!(h > 0.0, "Step size must be positive");
+
+    let mut p = x.to_vec();
+    (0..x.len())
+        .map(|i| {
+            p[i] = x[i] + h;
+            let fp = f(&p);
+            p[i] = x[i] - h;
+            let fm = f(&p);
+            p[i] = x[i];
+            (fp - fm) / (2.0 * h)
+        })
+        .collect()
+}
+
+/// Jacobian of `f` at `x` as an `m x n` matrix, where `m` is the length of
+/// `f(x)` and `n` the length of `x`; entry `(i, j)` is `d f_i / d x_j`.
+pub fn jacobian<F>(f: F, x: &[f64], h: f64) -> Matrix
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    assert!(h > 0.0, "Step size must be positive");
+
+    let n = x.len();
+    let m = f(x).len();
+    let mut jac = Matrix::zeros(m, n);
+    let mut p = x.to_vec();
+
+    for j in 0..n {
+        p[j] = x[j] + h;
+        let fp = f(&p);
+        p[j] = x[j] - h;
+        let fm = f(&p);
+        p[j] = x[j];
+
+        assert!(
+            fp.len() == m && fm.len() == m,
+            "Function output length must not depend on its input"
+        );
+        for i in 0..m {
+            jac.set(i, j, (fp[i] - fm[i]) / (2.0 * h));
+        }
+    }
+
+    jac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient_matches_analytic() {
+        // f(x, y, z) = x^2 y + sin(z) + e^(x z)
+        let f = |p: &[f64]| p[0] * p[0] * p[1] + p[2].sin() + (p[0] * p[2]).exp();
+        let grad = |p: &[f64]| {
+            let e = (p[0] * p[2]).exp();
+            vec![
+                2.0 * p[0] * p[1] + p[2] * e,
+                p[0] * p[0],
+                p[2].cos() + p[0] * e,
+            ]
+        };
+
+        for x in [[0.0, 0.0, 0.0], [1.0, -2.0, 0.5], [-0.7, 3.1, -1.2]] {
+            let got = gradient(f, &x, 1e-5);
+            for (g, w) in got.iter().zip(grad(&x)) {
+                assert!((g - w).abs() < 1e-7, "x = {x:?}: {g} vs {w}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobian_matches_analytic() {
+        // polar -> cartesian: (r cos t, r sin t, r)
+        let f = |p: &[f64]| vec![p[0] * p[1].cos(), p[0] * p[1].sin(), p[0]];
+        let (r, t) = (2.0f64, 0.6f64);
+        let want = [[t.cos(), -r * t.sin()], [t.sin(), r * t.cos()], [1.0, 0.0]];
+
+        let jac = jacobian(f, &[r, t], 1e-5);
+
+        assert_eq!((jac.rows, jac.cols), (3, 2));
+        for (i, row) in want.iter().enumerate() {
+            for (j, w) in row.iter().enumerate() {
+                assert!((jac.get(i, j) - w).abs() < 1e-8);
+            }
+        }
+    }
+}
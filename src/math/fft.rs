@@ -0,0 +1,133 @@
+use crate::math::complex::Complex;
+use std::f64::consts::PI;
+
+/// In-place forward discrete Fourier transform, `X_k = sum x_n e^(-2 pi i kn / N)`,
+/// by iterative radix-2 Cooley-Tukey. The length must be a power of two.
+pub fn fft(data: &mut [Complex<f64>]) {
+    transform(data, false);
+}
+
+/// In-place inverse of `fft`, including the `1 / N` normalization.
+pub fn ifft(data: &mut [Complex<f64>]) {
+    transform(data, true);
+
+    let scale = 1.0 / data.len() as f64;
+    for x in data.iter_mut() {
+        *x = x.scale(scale);
+    }
+}
+
+/// Copies `input` into a zero-padded buffer whose length is the next power of
+/// two, ready for `fft`.
+pub fn zero_pad(input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let mut padded = input.to_vec();
+    padded.resize(input.len().next_power_of_two(), Complex::zero());
+    padded
+}
+
+fn transform(data: &mut [Complex<f64>], inverse: bool) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    assert!(n.is_power_of_two(), "FFT length must be a power of two");
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    // twiddles for the full length; the stage of size `len` uses every
+    // (n / len)-th one. Computing each directly keeps large transforms accurate.
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let twiddles: Vec<Complex<f64>> = (0..n / 2)
+        .map(|k| Complex::from_polar(1.0, sign * 2.0 * PI * k as f64 / n as f64))
+        .collect();
+
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for chunk in data.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (k, (a, b)) in lo.iter_mut().zip(hi).enumerate() {
+                let t = *b * twiddles[k * stride];
+                *b = *a - t;
+                *a += t;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(got: &[Complex<f64>], want: &[Complex<f64>]) {
+        assert_eq!(got.len(), want.len());
+        for (g, w) in got.iter().zip(want) {
+            assert!((*g - *w).norm() < 1e-9, "got {g}, want {w}");
+        }
+    }
+
+    #[test]
+    fn test_constant_signal() {
+        let mut data = vec![Complex::from_real(3.0); 16];
+        fft(&mut data);
+
+        let mut want = vec![Complex::zero(); 16];
+        want[0] = Complex::from_real(48.0);
+        assert_close(&data, &want);
+    }
+
+    #[test]
+    fn test_single_tone() {
+        // cos(2 pi 3n / N) puts N/2 in bins 3 and N - 3
+        let n = 32;
+        let mut data: Vec<Complex<f64>> = (0..n)
+            .map(|k| Complex::from_real((2.0 * PI * 3.0 * k as f64 / n as f64).cos()))
+            .collect();
+        fft(&mut data);
+
+        for (k, x) in data.iter().enumerate() {
+            let want = if k == 3 || k == n - 3 { 16.0 } else { 0.0 };
+            assert!(
+                (x.re - want).abs() < 1e-9 && x.im.abs() < 1e-9,
+                "bin {k}: {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_inverse_roundtrip() {
+        let signal: Vec<Complex<f64>> = (0..100)
+            .map(|k| {
+                let t = k as f64;
+                Complex::new((t * 0.37).sin() + 0.5, (t * 1.3).cos() * t / 50.0)
+            })
+            .collect();
+
+        let mut data = zero_pad(&signal);
+        assert_eq!(data.len(), 128);
+        fft(&mut data);
+        ifft(&mut data);
+
+        assert_close(&data[..100], &signal);
+        assert_close(&data[100..], &[Complex::zero(); 28]);
+    }
+
+    #[test]
+    #[should_panic(expected = "FFT length must be a power of two")]
+    fn test_rejects_odd_length() {
+        fft(&mut [Complex::zero(); 6]);
+    }
+}
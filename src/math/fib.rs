@@ -1,4 +1,5 @@
 use num_bigint::BigInt;
+use num_traits::Zero;
 
 pub fn fib(n: isize) -> BigInt {
     if n < 0 {
@@ -7,6 +8,47 @@ pub fn fib(n: isize) -> BigInt {
     fib_luc(n).0
 }
 
+/// Writes `fib(n)` into `out`, running the fast doubling in place on `out`
+/// and a Lucas-number scratch value instead of building a fresh tree of
+/// temporaries as `fib` does.
+pub fn fib_into(n: isize, out: &mut BigInt) {
+    if n < 0 {
+        panic!("Fibonacci is not defined for negative numbers");
+    }
+    let n = n as usize;
+
+    // (F_k, L_k) held in (out, luc), starting from k = 0
+    out.set_zero();
+    let mut luc = BigInt::from(2);
+    let mut scratch = BigInt::ZERO;
+    let mut k_odd = false;
+
+    for bit in (0..usize::BITS - n.leading_zeros()).rev() {
+        // k -> 2k: F_2k = F_k L_k, L_2k = L_k^2 - 2 (-1)^k
+        *out *= &luc;
+        scratch.clone_from(&luc);
+        luc *= &scratch;
+        if k_odd {
+            luc += 2u32;
+        } else {
+            luc -= 2u32;
+        }
+        k_odd = false;
+
+        // k -> k + 1: F = (F + L) / 2, L = (5 F + L) / 2
+        if (n >> bit) & 1 == 1 {
+            scratch.clone_from(out);
+            scratch *= 5u32;
+            scratch += &luc;
+            *out += &luc;
+            *out >>= 1;
+            std::mem::swap(&mut luc, &mut scratch);
+            luc >>= 1;
+            k_odd = true;
+        }
+    }
+}
+
 fn fib_luc(mut n: isize) -> (BigInt, BigInt) {
     if n == 0 {
         return (BigInt::ZERO, BigInt::from(2));
@@ -30,6 +72,37 @@ fn fib_luc(mut n: isize) -> (BigInt, BigInt) {
     (&fib * &luc, &luc * &luc + BigInt::from(2) * k)
 }
 
+/// The Lucas sequences `(U_n(P, Q), V_n(P, Q))`, defined by
+/// `X_{k+2} = P X_{k+1} - Q X_k` with `U_0 = 0, U_1 = 1` and `V_0 = 2, V_1 = P`.
+/// Fibonacci and Lucas numbers are the `P = 1, Q = -1` case.
+///
+/// Computed by fast doubling over the bits of `n`, in O(log n) big-integer
+/// multiplications.
+pub fn lucas_sequence(p: i64, q: i64, n: u64) -> (BigInt, BigInt) {
+    let (p, q) = (BigInt::from(p), BigInt::from(q));
+    let d = &p * &p - BigInt::from(4) * &q;
+
+    // (U_k, V_k, Q^k), starting from k = 0
+    let (mut u, mut v, mut qk) = (BigInt::ZERO, BigInt::from(2), BigInt::from(1));
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        // k -> 2k
+        u = &u * &v;
+        v = &v * &v - BigInt::from(2) * &qk;
+        qk = &qk * &qk;
+
+        // k -> k + 1; both numerators are always even
+        if (n >> bit) & 1 == 1 {
+            let next_u = (&p * &u + &v) / 2;
+            v = (&d * &u + &p * &v) / 2;
+            u = next_u;
+            qk *= &q;
+        }
+    }
+
+    (u, v)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +128,58 @@ mod tests {
         assert_eq!(fib_luc(15).0, BigInt::from(610));
         assert_eq!(fib_luc(20).0, BigInt::from(6765));
     }
+
+    #[test]
+    fn test_fib_into_matches_fib() {
+        let mut out = BigInt::from(-12345);
+        for n in (0..100).chain([500, 1001, 4096, 20]) {
+            fib_into(n, &mut out);
+            assert_eq!(out, fib(n));
+        }
+    }
+
+    fn lucas_naive(p: i64, q: i64, n: u64) -> (BigInt, BigInt) {
+        let (p, q) = (BigInt::from(p), BigInt::from(q));
+        let (mut u, mut u_next) = (BigInt::from(0), BigInt::from(1));
+        let (mut v, mut v_next) = (BigInt::from(2), p.clone());
+        for _ in 0..n {
+            let u2 = &p * &u_next - &q * &u;
+            let v2 = &p * &v_next - &q * &v;
+            (u, u_next) = (u_next, u2);
+            (v, v_next) = (v_next, v2);
+        }
+        (u, v)
+    }
+
+    #[test]
+    fn test_lucas_sequence_fibonacci_case() {
+        for n in 0..200 {
+            assert_eq!(lucas_sequence(1, -1, n), fib_luc(n as isize), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_lucas_sequence_other_parameters() {
+        // Pell and Pell-Lucas numbers
+        let pell: Vec<_> = (0..7).map(|n| lucas_sequence(2, -1, n)).collect();
+        let (u, v): (Vec<_>, Vec<_>) = pell.into_iter().unzip();
+        assert_eq!(u, [0, 1, 2, 5, 12, 29, 70].map(BigInt::from));
+        assert_eq!(v, [2, 2, 6, 14, 34, 82, 198].map(BigInt::from));
+
+        // P = 3, Q = 2 gives 2^n - 1 and 2^n + 1
+        let (u, v) = lucas_sequence(3, 2, 100);
+        let two_100 = BigInt::from(1) << 100;
+        assert_eq!(u, &two_100 - 1);
+        assert_eq!(v, &two_100 + 1);
+
+        for (p, q) in [(4, 3), (-3, 5), (1, 1), (5, -7), (0, -2)] {
+            for n in [0, 1, 2, 7, 30, 61] {
+                assert_eq!(
+                    lucas_sequence(p, q, n),
+                    lucas_naive(p, q, n),
+                    "P={p} Q={q} n={n}"
+                );
+            }
+        }
+    }
 }
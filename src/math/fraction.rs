@@ -0,0 +1,197 @@
+use num_traits::{One, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact rational `num / den`, always in lowest terms with `den > 0`.
+///
+/// Arithmetic is carried out in `i128` and panics if the reduced result does
+/// not fit back into `i64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fraction {
+    num: i64,
+    den: i64,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Fraction {
+    pub fn new(num: i64, den: i64) -> Self {
+        Self::reduce(num as i128, den as i128)
+    }
+
+    pub const fn from_integer(n: i64) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    pub fn numer(&self) -> i64 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i64 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn recip(&self) -> Self {
+        Self::new(self.den, self.num)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    pub(crate) fn reduce(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Denominator must be nonzero");
+
+        let g = gcd(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+        let (num, den) = (sign * num / g, sign * den / g);
+
+        Self {
+            num: num.try_into().expect("Fraction overflows i64"),
+            den: den.try_into().expect("Fraction overflows i64"),
+        }
+    }
+}
+
+impl From<i64> for Fraction {
+    fn from(n: i64) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+impl Add for Fraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (a, b) = (self.num as i128, self.den as i128);
+        let (c, d) = (rhs.num as i128, rhs.den as i128);
+        Self::reduce(a * d + c * b, b * d)
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Fraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::reduce(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Div for Fraction {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.num != 0, "Division by zero fraction");
+        Self::reduce(
+            self.num as i128 * rhs.den as i128,
+            self.den as i128 * rhs.num as i128,
+        )
+    }
+}
+
+impl Neg for Fraction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::reduce(-(self.num as i128), self.den as i128)
+    }
+}
+
+impl Zero for Fraction {
+    fn zero() -> Self {
+        Self::from_integer(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+}
+
+impl One for Fraction {
+    fn one() -> Self {
+        Self::from_integer(1)
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so cross-multiplying keeps the order
+        (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_form() {
+        let f = Fraction::new(6, -8);
+        assert_eq!((f.numer(), f.denom()), (-3, 4));
+        assert_eq!(Fraction::new(0, -5), Fraction::zero());
+        assert_eq!(f.to_string(), "-3/4");
+        assert_eq!(Fraction::new(10, 5).to_string(), "2");
+        assert!(Fraction::new(10, 5).is_integer());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Fraction::new(1, 3);
+        let b = Fraction::new(1, 6);
+
+        assert_eq!(a + b, Fraction::new(1, 2));
+        assert_eq!(a - b, b);
+        assert_eq!(a * b, Fraction::new(1, 18));
+        assert_eq!(a / b, Fraction::from(2));
+        assert_eq!(-a, Fraction::new(-1, 3));
+        assert_eq!(a.recip(), Fraction::from(3));
+        assert!(b < a && -a < b);
+
+        // 0.1 + 0.2 is exact here
+        let sum = Fraction::new(1, 10) + Fraction::new(2, 10);
+        assert_eq!(sum, Fraction::new(3, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "Denominator must be nonzero")]
+    fn test_zero_denominator() {
+        Fraction::new(1, 0);
+    }
+}
@@ -1,3 +1,7 @@
+use num_bigint::{BigInt, Sign};
+use num_traits::{CheckedMul, One, Zero};
+
+use crate::math::random::Rng;
 use std::ops::Mul;
 
 #[inline]
@@ -20,6 +24,53 @@ where
     result
 }
 
+/// `fast_power` for integer types, returning `None` instead of wrapping or
+/// panicking when an intermediate product overflows.
+pub fn checked_fast_power<T>(base: T, mut exp: usize, identity: T) -> Option<T>
+where
+    T: Copy + CheckedMul,
+{
+    let mut result = identity;
+    let mut base = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(&base)?;
+        }
+        exp >>= 1;
+        // squaring past the last needed bit could overflow for nothing
+        if exp > 0 {
+            base = base.checked_mul(&base)?;
+        }
+    }
+    Some(result)
+}
+
+/// Binary exponentiation for types that are expensive to copy, such as
+/// `BigInt` or `Matrix`. `base` is cloned once; every product goes through
+/// `mul` by reference.
+pub fn fast_power_ref<T, F>(base: &T, mut exp: usize, identity: T, mul: F) -> T
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    let mut result = identity;
+    if exp == 0 {
+        return result;
+    }
+
+    let mut base = base.clone();
+    loop {
+        if exp & 1 == 1 {
+            result = mul(&result, &base);
+        }
+        exp >>= 1;
+        if exp == 0 {
+            return result;
+        }
+        base = mul(&base, &base);
+    }
+}
+
 pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
     if a == 0 {
         return (b, 0, 1);
@@ -41,6 +92,8 @@ pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
     }
 }
 
+/// `base^exp mod modulus`. Products are taken in `u128`, so any `u64`
+/// modulus is safe.
 pub fn mod_pow(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
     if modulus == 1 {
         return 0;
@@ -51,15 +104,83 @@ pub fn mod_pow(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
 
     while exp > 0 {
         if exp & 1 == 1 {
-            result = (result * base) % modulus;
+            result = mul_mod(result, base, modulus);
         }
-        base = (base * base) % modulus;
+        base = mul_mod(base, base, modulus);
         exp >>= 1;
     }
 
     result
 }
 
+/// `mod_pow` without the panic on a zero modulus. The products themselves
+/// cannot overflow, so a zero `modulus` is the only `None` case.
+pub fn checked_mod_pow(base: u64, exp: u64, modulus: u64) -> Option<u64> {
+    (modulus != 0).then(|| mod_pow(base, exp, modulus))
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+fn extended_gcd_big(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    if a.is_zero() {
+        return (b.clone(), BigInt::zero(), BigInt::one());
+    }
+
+    let (gcd, x1, y1) = extended_gcd_big(&(b % a), a);
+    let x = &y1 - (b / a) * &x1;
+
+    (gcd, x, x1)
+}
+
+pub fn mod_inverse_big(a: &BigInt, m: &BigInt) -> Option<BigInt> {
+    let a = ((a % m) + m) % m;
+    let (gcd, x, _) = extended_gcd_big(&a, m);
+    if !gcd.is_one() {
+        None
+    } else {
+        Some(((x % m) + m) % m)
+    }
+}
+
+/// `base^exp mod modulus` by square-and-multiply, always in `[0, modulus)`.
+///
+/// A negative `exp` raises the modular inverse of `base` to `-exp`.
+///
+/// # Panics
+/// Panics if `modulus` is not positive, or if `exp` is negative and `base`
+/// has no inverse modulo `modulus`.
+pub fn mod_pow_big(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> BigInt {
+    assert!(modulus.sign() == Sign::Plus, "Modulus must be positive");
+
+    if modulus.is_one() {
+        return BigInt::zero();
+    }
+
+    let mut base = ((base % modulus) + modulus) % modulus;
+    if exp.sign() == Sign::Minus {
+        base = mod_inverse_big(&base, modulus)
+            .expect("Base has no inverse modulo the modulus for a negative exponent");
+    }
+
+    let exp = exp.magnitude();
+    let mut result = BigInt::one();
+    for i in (0..exp.bits()).rev() {
+        result = &result * &result % modulus;
+        if exp.bit(i) {
+            result = result * &base % modulus;
+        }
+    }
+
+    result
+}
+
+/// Deterministic Miller-Rabin for every `u64`.
+///
+/// The witnesses are the first twelve primes, 2 through 37. Sorenson and
+/// Webster (2015) showed no composite below 3.18 * 10^23 is a strong
+/// pseudoprime to all of them, a bound well above `u64::MAX`.
 pub fn is_prime(n: u64) -> bool {
     if n <= 1 {
         return false;
@@ -99,6 +220,48 @@ pub fn is_prime(n: u64) -> bool {
     true
 }
 
+/// Probabilistic Miller-Rabin for arbitrary-precision `n` with `rounds`
+/// bases drawn from a PRNG seeded by `n`, so the answer is reproducible.
+///
+/// A composite passes with probability at most `4^-rounds`. Inputs that fit
+/// in a `u64` use the deterministic `is_prime` instead.
+pub fn is_prime_big(n: &BigInt, rounds: usize) -> bool {
+    if let Ok(small) = u64::try_from(n) {
+        return is_prime(small);
+    }
+    if n.sign() == Sign::Minus || !n.bit(0) {
+        return false;
+    }
+
+    let one = BigInt::one();
+    let n_minus_1 = n - &one;
+    let s = n_minus_1.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_1 >> s;
+
+    let mut rng = Rng::new(n.iter_u64_digits().fold(0, |acc, w| acc.rotate_left(7) ^ w));
+    let words = n.iter_u64_digits().len() + 1;
+
+    'witness: for _ in 0..rounds {
+        // uniform enough in [2, n - 2]: an extra word keeps the modulo bias negligible
+        let digits: Vec<u32> = (0..words * 2).map(|_| rng.next_u64() as u32).collect();
+        let a = BigInt::from_slice(Sign::Plus, &digits) % (n - 3u32) + 2u32;
+
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = &x * &x % n;
+            if x == n_minus_1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
 fn factor_power_of_two(n: u64) -> (u64, u64) {
     let mut d = n;
     let mut s = 0;
@@ -117,7 +280,7 @@ pub fn isqrt(n: u64) -> u64 {
     }
 
     let mut x = n;
-    let mut y = (x + 1) / 2;
+    let mut y = x / 2 + (x & 1); // (x + 1) / 2 without overflow at u64::MAX
 
     while y < x {
         x = y;
@@ -127,6 +290,34 @@ pub fn isqrt(n: u64) -> u64 {
     x
 }
 
+pub fn is_perfect_square(n: u64) -> bool {
+    let r = isqrt(n);
+    r * r == n
+}
+
+/// Floor of the `k`-th root of `n`, by Newton's iteration from an initial
+/// guess above the root.
+pub fn iroot(n: u64, k: u32) -> u64 {
+    assert!(k > 0, "Root degree must be positive");
+    if k == 1 || n <= 1 {
+        return n;
+    }
+
+    let bits = u64::BITS - n.leading_zeros();
+    let (n, k) = (n as u128, k as u128);
+    let mut x = 1u128 << bits.div_ceil(k as u32);
+
+    loop {
+        // n / x^(k - 1), which is 0 once the power exceeds u128
+        let quotient = x.checked_pow(k as u32 - 1).map_or(0, |p| n / p);
+        let y = ((k - 1) * x + quotient) / k;
+        if y >= x {
+            return x as u64;
+        }
+        x = y;
+    }
+}
+
 /// Prime factorization using trial division and wheel factorization
 pub fn prime_factors(mut n: u64) -> Vec<u64> {
     let mut factors = Vec::new();
@@ -160,3 +351,209 @@ pub fn prime_factors(mut n: u64) -> Vec<u64> {
 
     factors
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::matrix::Matrix;
+    use crate::math::matrix::ops::mul;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_fast_power_ref_bigint() {
+        let three = BigInt::from(3);
+        let got = fast_power_ref(&three, 100, BigInt::from(1), |a, b| a * b);
+
+        assert_eq!(
+            got.to_string(),
+            "515377520732011331036461129765621272702107522001"
+        );
+        assert_eq!(
+            fast_power_ref(&three, 0, BigInt::from(1), |a, b| a * b),
+            BigInt::from(1)
+        );
+    }
+
+    #[test]
+    fn test_fast_power_ref_matrix() {
+        // [[1, 1], [1, 0]]^n = [[F(n+1), F(n)], [F(n), F(n-1)]]
+        let q = Matrix::new(2, 2, vec![1.0, 1.0, 1.0, 0.0]);
+        let q30 = fast_power_ref(&q, 30, Matrix::identity(2), |a, b| mul(a, b).unwrap());
+
+        assert_eq!(q30.data, vec![1346269.0, 832040.0, 832040.0, 514229.0]);
+        assert_eq!(
+            fast_power(7u64, 5, 1),
+            fast_power_ref(&7u64, 5, 1, |a, b| a * b)
+        );
+    }
+
+    #[test]
+    fn test_mod_pow_big() {
+        let big = |n: i64| BigInt::from(n);
+
+        assert_eq!(mod_pow_big(&big(4), &big(13), &big(497)), big(445));
+        assert_eq!(mod_pow_big(&big(2), &big(10), &big(1000)), big(24));
+        assert_eq!(mod_pow_big(&big(7), &big(0), &big(13)), big(1));
+        assert_eq!(mod_pow_big(&big(7), &big(5), &big(1)), big(0));
+        assert_eq!(mod_pow_big(&big(-2), &big(3), &big(5)), big(2));
+
+        // matches the u64 version
+        for (b, e, m) in [(3u64, 200u64, 1_000_003u64), (123_456, 789, 65_537)] {
+            assert_eq!(
+                mod_pow_big(&big(b as i64), &big(e as i64), &big(m as i64)),
+                big(mod_pow(b, e, m) as i64)
+            );
+        }
+
+        // Fermat: a^(p-1) = 1 mod p for the Mersenne prime 2^127 - 1
+        let p: BigInt = (BigInt::one() << 127) - 1;
+        assert_eq!(mod_pow_big(&big(3), &(&p - 1), &p), big(1));
+    }
+
+    #[test]
+    fn test_mod_pow_big_negative_exponent() {
+        let big = |n: i64| BigInt::from(n);
+
+        // 3^-1 = 5 mod 7, so 3^-2 = 25 = 4 mod 7
+        assert_eq!(mod_pow_big(&big(3), &big(-1), &big(7)), big(5));
+        assert_eq!(mod_pow_big(&big(3), &big(-2), &big(7)), big(4));
+        assert_eq!(mod_inverse_big(&big(4), &big(8)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Base has no inverse")]
+    fn test_mod_pow_big_no_inverse() {
+        mod_pow_big(&BigInt::from(4), &BigInt::from(-1), &BigInt::from(8));
+    }
+
+    #[test]
+    fn test_mod_pow_no_overflow() {
+        let m = u64::MAX - 58; // largest u64 prime
+        assert_eq!(mod_pow(m - 1, 2, m), 1);
+        assert_eq!(mod_pow(2, m - 1, m), 1);
+    }
+
+    #[test]
+    fn test_checked_fast_power() {
+        assert_eq!(checked_fast_power(3u64, 40, 1), Some(3u64.pow(40)));
+        assert_eq!(checked_fast_power(3u64, 41, 1), None);
+        assert_eq!(checked_fast_power(2u64, 63, 1), Some(1 << 63));
+        assert_eq!(checked_fast_power(2u64, 64, 1), None);
+        assert_eq!(checked_fast_power(-2i8, 7, 1), Some(-128));
+        assert_eq!(checked_fast_power(2i8, 7, 1), None);
+
+        // only the squares actually used may overflow the check
+        assert_eq!(checked_fast_power(u32::MAX, 1, 1), Some(u32::MAX));
+        assert_eq!(checked_fast_power(u32::MAX, 0, 1), Some(1));
+    }
+
+    #[test]
+    fn test_checked_mod_pow() {
+        assert_eq!(checked_mod_pow(4, 13, 497), Some(445));
+        assert_eq!(checked_mod_pow(u64::MAX, u64::MAX, u64::MAX), Some(0));
+        assert_eq!(checked_mod_pow(2, 10, 0), None);
+    }
+
+    #[test]
+    fn test_is_prime_matches_trial_division() {
+        let trial = |n: u64| {
+            n >= 2
+                && (2..)
+                    .take_while(|d| d * d <= n)
+                    .all(|d| !n.is_multiple_of(d))
+        };
+        for n in 0..20_000 {
+            assert_eq!(is_prime(n), trial(n), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_is_prime_full_u64_range() {
+        // largest primes below 2^64 and 2^63, and a Mersenne prime
+        for p in [u64::MAX - 58, (1 << 63) - 25, (1 << 61) - 1, 4_294_967_291] {
+            assert!(is_prime(p), "{p} is prime");
+        }
+
+        // Carmichael numbers and strong pseudoprimes to many small bases
+        for c in [
+            561,
+            1105,
+            1729,
+            2465,
+            2821,
+            6601,
+            8911,
+            3_215_031_751,
+            2_152_302_898_747,
+            3_474_749_660_383,
+            341_550_071_728_321,
+            3_825_123_056_546_413_051,
+            u64::MAX,
+            (1 << 61) + 1,
+            4_294_967_291 * 4_294_967_279,
+        ] {
+            assert!(!is_prime(c), "{c} is composite");
+        }
+    }
+
+    #[test]
+    fn test_is_prime_big() {
+        let two = BigInt::from(2);
+        let mersenne = |p: u32| two.pow(p) - 1;
+
+        for p in [89, 107, 127, 521, 607] {
+            assert!(is_prime_big(&mersenne(p), 20), "2^{p} - 1 is prime");
+        }
+        for p in [67, 101, 257] {
+            assert!(!is_prime_big(&mersenne(p), 20), "2^{p} - 1 is composite");
+        }
+
+        // a semiprime, and strong pseudoprimes to every base up to 37 and 41,
+        // which a fixed witness set would accept
+        assert!(!is_prime_big(&(mersenne(89) * mersenne(107)), 20));
+        for c in ["318665857834031151167461", "3317044064679887385961981"] {
+            assert!(!is_prime_big(&c.parse().unwrap(), 20), "{c} is composite");
+        }
+
+        assert!(is_prime_big(&BigInt::from(97), 5));
+        assert!(!is_prime_big(&BigInt::from(-7), 5));
+        assert!(!is_prime_big(&(two.pow(100)), 5));
+    }
+
+    #[test]
+    fn test_perfect_squares() {
+        assert!(is_perfect_square(144));
+        assert!(!is_perfect_square(145));
+        assert!(is_perfect_square(0) && is_perfect_square(1));
+        assert!(!is_perfect_square(2));
+
+        let root = u32::MAX as u64;
+        assert!(is_perfect_square(root * root));
+        assert!(!is_perfect_square(root * root - 1));
+        assert_eq!(isqrt(u64::MAX), root);
+    }
+
+    #[test]
+    fn test_iroot() {
+        assert_eq!(iroot(27, 3), 3);
+        assert_eq!(iroot(26, 3), 2);
+        assert_eq!(iroot(1 << 60, 5), 1 << 12);
+        assert_eq!(iroot(u64::MAX, 2), isqrt(u64::MAX));
+        assert_eq!(iroot(u64::MAX, 64), 1);
+        assert_eq!(iroot(u64::MAX, 1), u64::MAX);
+        assert_eq!(iroot(0, 7), 0);
+
+        let mut rng = Rng::new(23);
+        for _ in 0..2000 {
+            let n = rng.next_u64() >> rng.gen_range(64);
+            let k = rng.gen_range(12) as u32 + 1;
+            let r = iroot(n, k) as u128;
+
+            assert!(r.pow(k) <= n as u128, "iroot({n}, {k}) = {r}");
+            assert!(
+                (r + 1).checked_pow(k).is_none_or(|p| p > n as u128),
+                "iroot({n}, {k}) = {r}"
+            );
+        }
+    }
+}
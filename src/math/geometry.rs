@@ -0,0 +1,351 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec2 {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// z-component of the 3D cross product; positive when `other` is
+    /// counterclockwise from `self`.
+    pub fn cross(self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        assert!(len != 0.0, "Cannot normalize a zero vector");
+        self * (1.0 / len)
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        (self - other).length()
+    }
+
+    pub fn angle_between(self, other: Self) -> f64 {
+        let mags = self.length() * other.length();
+        assert!(mags != 0.0, "Cannot compute angle with zero vector");
+        (self.dot(other) / mags).clamp(-1.0, 1.0).acos()
+    }
+}
+
+impl Vec3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        assert!(len != 0.0, "Cannot normalize a zero vector");
+        self * (1.0 / len)
+    }
+
+    pub fn distance(self, other: Self) -> f64 {
+        (self - other).length()
+    }
+
+    pub fn angle_between(self, other: Self) -> f64 {
+        let mags = self.length() * other.length();
+        assert!(mags != 0.0, "Cannot compute angle with zero vector");
+        (self.dot(other) / mags).clamp(-1.0, 1.0).acos()
+    }
+}
+
+macro_rules! impl_vec_ops {
+    ($ty:ident { $($field:ident),+ }) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<f64> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+impl_vec_ops!(Vec2 { x, y });
+impl_vec_ops!(Vec3 { x, y, z });
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intersection {
+    None,
+    Point(Vec2),
+    /// The segments are collinear and share this sub-segment.
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.start.distance(self.end)
+    }
+
+    pub fn intersect(&self, other: &Segment) -> Intersection {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let qp = other.start - self.start;
+        let denom = r.cross(s);
+
+        if denom.abs() < EPSILON {
+            if qp.cross(r).abs() >= EPSILON {
+                return Intersection::None; // parallel, never touching
+            }
+            return collinear_overlap(self, other);
+        }
+
+        let t = qp.cross(s) / denom;
+        let u = qp.cross(r) / denom;
+
+        if (-EPSILON..=1.0 + EPSILON).contains(&t) && (-EPSILON..=1.0 + EPSILON).contains(&u) {
+            Intersection::Point(self.start + r * t)
+        } else {
+            Intersection::None
+        }
+    }
+}
+
+impl Ray {
+    pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn at(&self, t: f64) -> Vec2 {
+        self.origin + self.direction * t
+    }
+
+    /// Nearest point where the ray meets `segment`, if any.
+    pub fn intersect_segment(&self, segment: &Segment) -> Option<Vec2> {
+        let r = self.direction;
+        let s = segment.end - segment.start;
+        let qp = segment.start - self.origin;
+        let denom = r.cross(s);
+
+        if denom.abs() < EPSILON {
+            if qp.cross(r).abs() >= EPSILON {
+                return None;
+            }
+
+            let rr = r.dot(r);
+            if rr == 0.0 {
+                return None;
+            }
+
+            let t0 = qp.dot(r) / rr;
+            let t1 = (segment.end - self.origin).dot(r) / rr;
+            let (lo, hi) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+            if hi < -EPSILON {
+                return None;
+            }
+            return Some(self.at(lo.max(0.0)));
+        }
+
+        let t = qp.cross(s) / denom;
+        let u = qp.cross(r) / denom;
+
+        if t >= -EPSILON && (-EPSILON..=1.0 + EPSILON).contains(&u) {
+            Some(self.at(t.max(0.0)))
+        } else {
+            None
+        }
+    }
+}
+
+fn collinear_overlap(a: &Segment, b: &Segment) -> Intersection {
+    let r = a.end - a.start;
+    let rr = r.dot(r);
+
+    if rr == 0.0 {
+        return if point_on_segment(a.start, b) {
+            Intersection::Point(a.start)
+        } else {
+            Intersection::None
+        };
+    }
+
+    let t0 = (b.start - a.start).dot(r) / rr;
+    let t1 = (b.end - a.start).dot(r) / rr;
+    let lo = t0.min(t1).max(0.0);
+    let hi = t0.max(t1).min(1.0);
+
+    if lo > hi + EPSILON {
+        Intersection::None
+    } else if (hi - lo) * rr.sqrt() < EPSILON {
+        Intersection::Point(a.start + r * lo)
+    } else {
+        Intersection::Overlap(Segment::new(a.start + r * lo, a.start + r * hi))
+    }
+}
+
+fn point_on_segment(p: Vec2, seg: &Segment) -> bool {
+    let r = seg.end - seg.start;
+    let d = p - seg.start;
+
+    if r.cross(d).abs() >= EPSILON {
+        return false;
+    }
+
+    let t = d.dot(r);
+    t >= -EPSILON && t <= r.dot(r) + EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_cross_is_orthogonal() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-4.0, 0.5, 2.0);
+        let c = a.cross(b);
+
+        assert!(approx(c.dot(a), 0.0));
+        assert!(approx(c.dot(b), 0.0));
+        assert_eq!(
+            Vec3::new(1.0, 0.0, 0.0).cross(Vec3::new(0.0, 1.0, 0.0)),
+            Vec3::new(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_length_distance_angle() {
+        let a = Vec2::new(3.0, 4.0);
+        assert!(approx(a.length(), 5.0));
+        assert!(approx(a.normalize().length(), 1.0));
+        assert!(approx(a.distance(Vec2::new(0.0, 0.0)), 5.0));
+        assert!(approx(
+            Vec2::new(1.0, 0.0).angle_between(Vec2::new(0.0, 2.0)),
+            std::f64::consts::FRAC_PI_2
+        ));
+    }
+
+    #[test]
+    fn test_segment_crossing() {
+        let a = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+        let b = Segment::new(Vec2::new(0.0, 2.0), Vec2::new(2.0, 0.0));
+        assert_eq!(a.intersect(&b), Intersection::Point(Vec2::new(1.0, 1.0)));
+
+        let c = Segment::new(Vec2::new(3.0, 0.0), Vec2::new(3.0, 5.0));
+        assert_eq!(a.intersect(&c), Intersection::None);
+    }
+
+    #[test]
+    fn test_segment_parallel() {
+        let a = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0));
+        let b = Segment::new(Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0));
+        assert_eq!(a.intersect(&b), Intersection::None);
+    }
+
+    #[test]
+    fn test_segment_collinear() {
+        let a = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0));
+
+        let overlapping = Segment::new(Vec2::new(3.0, 0.0), Vec2::new(1.0, 0.0));
+        assert_eq!(
+            a.intersect(&overlapping),
+            Intersection::Overlap(Segment::new(Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)))
+        );
+
+        let touching = Segment::new(Vec2::new(2.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(
+            a.intersect(&touching),
+            Intersection::Point(Vec2::new(2.0, 0.0))
+        );
+
+        let disjoint = Segment::new(Vec2::new(3.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(a.intersect(&disjoint), Intersection::None);
+    }
+
+    #[test]
+    fn test_ray_segment() {
+        let ray = Ray::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0));
+        let wall = Segment::new(Vec2::new(5.0, -1.0), Vec2::new(5.0, 1.0));
+        assert_eq!(ray.intersect_segment(&wall), Some(Vec2::new(5.0, 0.0)));
+
+        let behind = Segment::new(Vec2::new(-5.0, -1.0), Vec2::new(-5.0, 1.0));
+        assert_eq!(ray.intersect_segment(&behind), None);
+
+        let along = Segment::new(Vec2::new(2.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(ray.intersect_segment(&along), Some(Vec2::new(2.0, 0.0)));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::math::complex::Complex;
+use crate::math::matrix::matrix::Matrix;
+use crate::math::matrix::utils::error::Error;
+
+/// A row-major matrix of `Complex<f64>` entries, laid out like `Matrix`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<Complex>,
+}
+
+impl ComplexMatrix {
+    pub fn new(rows: usize, cols: usize, data: Vec<Complex>) -> Self {
+        Self { rows, cols, data }
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![Complex::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, Complex::one());
+        }
+        m
+    }
+
+    pub fn from_real(m: &Matrix) -> Self {
+        Self {
+            rows: m.rows,
+            cols: m.cols,
+            data: m.data.iter().map(|&x| Complex::from_real(x)).collect(),
+        }
+    }
+
+    pub fn real_part(&self) -> Matrix {
+        Matrix::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|z| z.re).collect(),
+        )
+    }
+
+    pub fn imag_part(&self) -> Matrix {
+        Matrix::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|z| z.im).collect(),
+        )
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Complex {
+        self.data[row * self.cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: Complex) {
+        self.data[row * self.cols + col] = value;
+    }
+
+    pub fn add(&self, other: &ComplexMatrix) -> Result<ComplexMatrix, Error> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(Error::MatrixSizeMismatch);
+        }
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(&a, &b)| a + b)
+            .collect();
+        Ok(Self::new(self.rows, self.cols, data))
+    }
+
+    pub fn mul(&self, other: &ComplexMatrix) -> Result<ComplexMatrix, Error> {
+        if self.cols != other.rows {
+            return Err(Error::MatrixSizeMismatch);
+        }
+        let mut result = Self::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for j in 0..other.cols {
+                let mut sum = Complex::zero();
+                for k in 0..self.cols {
+                    sum += self.get(i, k) * other.get(k, j);
+                }
+                result.set(i, j, sum);
+            }
+        }
+        Ok(result)
+    }
+
+    /// The Hermitian adjoint `A*`: transpose with every entry conjugated.
+    pub fn conjugate_transpose(&self) -> ComplexMatrix {
+        let mut result = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                result.set(j, i, self.get(i, j).conj());
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_identity(m: &ComplexMatrix) {
+        assert_eq!(m.rows, m.cols);
+        for i in 0..m.rows {
+            for j in 0..m.cols {
+                let want = if i == j { 1.0 } else { 0.0 };
+                let z = m.get(i, j);
+                assert!((z.re - want).abs() < 1e-12 && z.im.abs() < 1e-12);
+            }
+        }
+    }
+
+    // normalized DFT matrix, F[j][k] = w^(jk) / sqrt(n)
+    fn dft(n: usize) -> ComplexMatrix {
+        let mut m = ComplexMatrix::zeros(n, n);
+        for j in 0..n {
+            for k in 0..n {
+                let theta = -2.0 * PI * (j * k) as f64 / n as f64;
+                m.set(j, k, Complex::from_polar(1.0 / (n as f64).sqrt(), theta));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_unitary_times_adjoint_is_identity() {
+        let h = 1.0 / 2f64.sqrt();
+        let u = ComplexMatrix::new(
+            2,
+            2,
+            vec![
+                Complex::new(h, 0.0),
+                Complex::new(0.0, h),
+                Complex::new(0.0, h),
+                Complex::new(h, 0.0),
+            ],
+        );
+
+        assert_identity(&u.mul(&u.conjugate_transpose()).unwrap());
+        assert_identity(&u.conjugate_transpose().mul(&u).unwrap());
+
+        let f = dft(8);
+        assert_identity(&f.mul(&f.conjugate_transpose()).unwrap());
+    }
+
+    #[test]
+    fn test_real_roundtrip() {
+        let m = Matrix::new(2, 3, vec![1.0, -2.0, 3.0, 4.0, 5.5, 6.0]);
+        let c = ComplexMatrix::from_real(&m);
+
+        assert_eq!(c.real_part(), m);
+        assert_eq!(c.imag_part(), Matrix::zeros(2, 3));
+
+        let sum = c.add(&ComplexMatrix::identity(2).mul(&c).unwrap()).unwrap();
+        assert_eq!(sum.real_part().data, vec![2.0, -4.0, 6.0, 8.0, 11.0, 12.0]);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let a = ComplexMatrix::zeros(2, 3);
+        let b = ComplexMatrix::zeros(2, 2);
+
+        assert!(matches!(a.add(&b), Err(Error::MatrixSizeMismatch)));
+        assert!(matches!(a.mul(&b), Err(Error::MatrixSizeMismatch)));
+        assert!(b.mul(&a).is_ok());
+    }
+}
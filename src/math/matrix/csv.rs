@@ -0,0 +1,303 @@
+//! Delimited text import and export for `Matrix`.
+//!
+//! Fields may be wrapped in double quotes, inside which the delimiter and
+//! newlines are literal and `""` stands for one quote. Blank lines are skipped.
+
+use crate::math::matrix::matrix::Matrix;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// Failure to read a CSV matrix. Line numbers are 1-based and refer to the
+/// line a record starts on.
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    UnterminatedQuote(usize),
+    InvalidNumber {
+        line: usize,
+        field: String,
+    },
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(err) => write!(f, "I/O error: {err}"),
+            ParseError::UnterminatedQuote(line) => {
+                write!(f, "Unterminated quoted field starting on line {line}")
+            }
+            ParseError::InvalidNumber { line, field } => {
+                write!(f, "Invalid number {field:?} on line {line}")
+            }
+            ParseError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {expected} fields on line {line}, found {found}"
+            ),
+        }
+    }
+}
+
+impl StdError for ParseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ParseError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+impl Matrix {
+    /// Reads comma-separated rows, taking the first record as column names
+    /// when `has_header` is set.
+    pub fn from_csv(
+        r: &mut impl Read,
+        has_header: bool,
+    ) -> Result<(Matrix, Option<Vec<String>>), ParseError> {
+        Self::from_csv_with_delimiter(r, has_header, ',')
+    }
+
+    pub fn from_csv_with_delimiter(
+        r: &mut impl Read,
+        has_header: bool,
+        delimiter: char,
+    ) -> Result<(Matrix, Option<Vec<String>>), ParseError> {
+        let mut text = String::new();
+        r.read_to_string(&mut text)?;
+        let mut records = parse_records(&text, delimiter)?.into_iter();
+
+        let header = if has_header {
+            records.next().map(|(_, fields)| fields)
+        } else {
+            None
+        };
+        let mut cols = header.as_ref().map(Vec::len);
+
+        let mut rows = 0;
+        let mut data = Vec::new();
+        for (line, fields) in records {
+            let expected = *cols.get_or_insert(fields.len());
+            if fields.len() != expected {
+                return Err(ParseError::RaggedRow {
+                    line,
+                    expected,
+                    found: fields.len(),
+                });
+            }
+            for field in fields {
+                let value = field
+                    .trim()
+                    .parse()
+                    .map_err(|_| ParseError::InvalidNumber { line, field })?;
+                data.push(value);
+            }
+            rows += 1;
+        }
+
+        Ok((Matrix::new(rows, cols.unwrap_or(0), data), header))
+    }
+
+    /// Writes comma-separated rows, preceded by `header` if given. Header
+    /// names are quoted when they contain the delimiter, a quote or a newline.
+    pub fn write_csv(&self, w: &mut impl Write, header: Option<&[&str]>) -> io::Result<()> {
+        self.write_csv_with_delimiter(w, header, ',')
+    }
+
+    /// Writes comma-separated rows with no header, the format `from_csv`
+    /// reads back.
+    pub fn to_csv(&self, w: &mut impl Write) -> io::Result<()> {
+        self.write_csv(w, None)
+    }
+
+    pub fn write_csv_with_delimiter(
+        &self,
+        w: &mut impl Write,
+        header: Option<&[&str]>,
+        delimiter: char,
+    ) -> io::Result<()> {
+        if let Some(names) = header {
+            assert_eq!(names.len(), self.cols, "Header must name every column");
+            let quoted: Vec<String> = names.iter().map(|n| quote(n, delimiter)).collect();
+            writeln!(w, "{}", quoted.join(&delimiter.to_string()))?;
+        }
+
+        for row in self.data.chunks(self.cols.max(1)) {
+            let fields: Vec<String> = row.iter().map(f64::to_string).collect();
+            writeln!(w, "{}", fields.join(&delimiter.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// splits text into records of unquoted fields, each tagged with its line
+fn parse_records(text: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, ParseError> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                if fields.len() > 1 || !fields[0].trim().is_empty() {
+                    records.push((start, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                start = line;
+            }
+            _ if c == delimiter => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(ParseError::UnterminatedQuote(start));
+    }
+    if !fields.is_empty() || !field.trim().is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Matrix {
+        Matrix::new(3, 2, vec![1.0, -2.5, 0.1, 1e-300, 42.0, f64::MIN_POSITIVE])
+    }
+
+    #[test]
+    fn test_roundtrip_without_header() {
+        let m = sample();
+        let mut buf = Vec::new();
+        m.write_csv(&mut buf, None).unwrap();
+
+        let (parsed, header) = Matrix::from_csv(&mut buf.as_slice(), false).unwrap();
+        assert_eq!(parsed, m);
+        assert_eq!(header, None);
+    }
+
+    #[test]
+    fn test_to_csv_and_numpy_output() {
+        let m = Matrix::new(2, 3, vec![0.5, -1.0, 1.0 / 3.0, 3.0, 1e20, -0.1]);
+        let mut buf = Vec::new();
+        m.to_csv(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "0.5,-1,0.3333333333333333\n3,100000000000000000000,-0.1\n"
+        );
+        // shortest round-trip formatting reads back bit for bit
+        let (parsed, header) = Matrix::from_csv(&mut buf.as_slice(), false).unwrap();
+        assert_eq!(parsed, m);
+        assert!(header.is_none());
+
+        // as written by `np.savetxt(f, a, delimiter=",")`
+        let text = "1.000000000000000000e+00,-2.500000000000000000e-01\n\
+                    3.000000000000000000e+00,4.000000000000000000e+00\n";
+        let (parsed, _) = Matrix::from_csv(&mut text.as_bytes(), false).unwrap();
+        assert_eq!(parsed, Matrix::new(2, 2, vec![1.0, -0.25, 3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_roundtrip_with_header() {
+        let m = sample();
+        let names = ["width", "say \"hi\"; bye"];
+        let mut buf = Vec::new();
+        m.write_csv_with_delimiter(&mut buf, Some(&names), ';')
+            .unwrap();
+
+        let text = String::from_utf8(buf.clone()).unwrap();
+        assert!(text.starts_with("width;\"say \"\"hi\"\"; bye\"\n1;-2.5\n"));
+
+        let (parsed, header) =
+            Matrix::from_csv_with_delimiter(&mut buf.as_slice(), true, ';').unwrap();
+        assert_eq!(parsed, m);
+        assert_eq!(header.unwrap(), names);
+    }
+
+    #[test]
+    fn test_quoted_fields_and_blank_lines() {
+        let csv = "\"a,1\",\"multi\r\nline\"\r\n\r\n\" 1.5\",2\r\n3,\"4\"";
+        let (m, header) = Matrix::from_csv(&mut csv.as_bytes(), true).unwrap();
+
+        assert_eq!(header.unwrap(), ["a,1", "multi\r\nline"]);
+        assert_eq!((m.rows, m.cols, m.data), (2, 2, vec![1.5, 2.0, 3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let parse = |s: &str| Matrix::from_csv(&mut s.as_bytes(), false);
+
+        assert!(matches!(
+            parse("1,2\n3,4,5\n"),
+            Err(ParseError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        ));
+        assert!(matches!(
+            parse("1,x\n"),
+            Err(ParseError::InvalidNumber { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse("1,2\n\"3,4\n"),
+            Err(ParseError::UnterminatedQuote(2))
+        ));
+
+        // a header fixes the width even before any data
+        assert!(matches!(
+            Matrix::from_csv(&mut "a,b,c\n1,2\n".as_bytes(), true),
+            Err(ParseError::RaggedRow { line: 2, .. })
+        ));
+    }
+}
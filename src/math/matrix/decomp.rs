@@ -1,5 +1,8 @@
-use crate::matrix::matrix::Matrix;
-use crate::utils::error::Error;
+use crate::math::matrix::field::lu_in_place;
+use crate::math::matrix::matrix::Matrix;
+use crate::math::matrix::ops::{dot, magnitude, mul, normalize, scalar_mul, sub};
+use crate::math::matrix::utils::error::Error;
+use crate::math::random::Rng;
 
 pub fn lu(matrix: &Matrix) -> Result<(Matrix, Matrix), Error> {
     if matrix.rows != matrix.cols {
@@ -29,14 +32,405 @@ pub fn lu(matrix: &Matrix) -> Result<(Matrix, Matrix), Error> {
     Ok((lower, upper))
 }
 
+/// LU decomposition with partial pivoting, `P A = L U`.
+///
+/// Returns `(perm, lower, upper, swaps)` where row `i` of `P A` is row
+/// `perm[i]` of `A` and `swaps` is the number of row exchanges performed, so
+/// `det(P) = (-1)^swaps`. A pivot below `n * EPSILON * max|a|` counts as
+/// zero, so the singularity test does not depend on the matrix's scale.
+pub fn plu(matrix: &Matrix) -> Result<(Vec<usize>, Matrix, Matrix, usize), Error> {
+    if matrix.rows != matrix.cols {
+        return Err(Error::MatrixNotSquare);
+    }
+
+    let n = matrix.rows;
+    let max_abs = matrix.data.iter().map(|x| x.abs()).fold(0.0, f64::max);
+    let tol = n as f64 * f64::EPSILON * max_abs;
+
+    let mut upper = matrix.clone();
+    let (perm, lower, swaps) = lu_in_place(&mut upper.data, n, tol)?;
+
+    Ok((perm, Matrix::new(n, n, lower), upper, swaps))
+}
+
 pub fn svd(matrix: &Matrix) -> Result<(Matrix, Vec<f64>, Matrix), Error> {
-    return Ok((matrix.clone(), vec![], matrix.clone()));
+    Ok((matrix.clone(), vec![], matrix.clone()))
 }
 
 pub fn qr(matrix: &Matrix) -> Result<(Matrix, Matrix), Error> {
-    return Ok((matrix.clone(), matrix.clone()));
+    Ok((matrix.clone(), matrix.clone()))
 }
 
 pub fn eigen(matrix: &Matrix) -> Result<(Matrix, Matrix), Error> {
-    return Ok((matrix.clone(), matrix.clone()));
+    Ok((matrix.clone(), matrix.clone()))
+}
+
+/// Dominant eigenpair of a square matrix by power iteration.
+///
+/// The start vector is drawn from a fixed-seed `Rng` rather than being
+/// uniform, so it is not itself an eigenvector of structured inputs such as
+/// graph Laplacians, and results stay reproducible.
+///
+/// Stops once the residual `|m v - lambda v|` is at most `tol`, returning the
+/// Rayleigh quotient `lambda` and the unit column `v`, or `None` if that does
+/// not happen within `iters` steps.
+pub fn power_iteration(m: &Matrix, iters: usize, tol: f64) -> Option<(f64, Matrix)> {
+    assert_eq!(m.rows, m.cols, "Matrix must be square");
+    if m.rows == 0 {
+        return None;
+    }
+
+    let mut rng = Rng::new(0x5eed);
+    let start = (0..m.rows).map(|_| rng.next_f64() - 0.5).collect();
+    let mut v = normalize(&Matrix::new(m.rows, 1, start));
+    for _ in 0..iters {
+        let w = mul(m, &v).ok()?;
+        let lambda = dot(&v, &w);
+        let residual = magnitude(&sub(&w, &scalar_mul(&v, lambda)).ok()?);
+        if residual <= tol {
+            return Some((lambda, v));
+        }
+        v = normalize(&w);
+    }
+    None
+}
+
+/// Eigenvalues of the symmetric tridiagonal matrix with main diagonal `diag`
+/// and sub/super-diagonal `off_diag`, via the QL algorithm with implicit
+/// Wilkinson shifts. Returned in ascending order.
+pub fn tridiagonal_eigenvalues(diag: &[f64], off_diag: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    assert!(
+        off_diag.len() + 1 == n || (n == 0 && off_diag.is_empty()),
+        "Off-diagonal must have one fewer element than the diagonal"
+    );
+
+    let mut d = diag.to_vec();
+    let mut e = off_diag.to_vec();
+    e.push(0.0);
+
+    for l in 0..n {
+        let mut iter = 0;
+        loop {
+            // find the first negligible off-diagonal element at or after l
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= f64::EPSILON * dd {
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+
+            iter += 1;
+            assert!(iter <= 60, "Tridiagonal QL failed to converge");
+
+            let mut g = (d[l + 1] - d[l]) / (2.0 * e[l]);
+            let mut r = g.hypot(1.0);
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+
+            let (mut s, mut c, mut p) = (1.0, 1.0, 0.0);
+            let mut deflated = false;
+
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == 0.0 {
+                    d[i + 1] -= p;
+                    e[m] = 0.0;
+                    deflated = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2.0 * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+            }
+
+            if deflated {
+                continue;
+            }
+            d[l] -= p;
+            e[l] = g;
+            e[m] = 0.0;
+        }
+    }
+
+    d.sort_by(f64::total_cmp);
+    d
+}
+
+/// Householder reduction of a symmetric matrix `A` to tridiagonal form
+/// `T = Q^T A Q`.
+///
+/// Returns the diagonal and off-diagonal of `T` together with the orthogonal
+/// `Q`, so that `A = Q T Q^T`. Only meaningful for symmetric input.
+pub fn to_tridiagonal(matrix: &Matrix) -> Result<(Vec<f64>, Vec<f64>, Matrix), Error> {
+    if matrix.rows != matrix.cols {
+        return Err(Error::MatrixNotSquare);
+    }
+
+    let n = matrix.rows;
+    let mut a = matrix.clone();
+    let mut q = Matrix::identity(n);
+
+    for k in 0..n.saturating_sub(2) {
+        let norm = ((k + 1)..n)
+            .map(|i| a.get(i, k).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        if norm < 1e-300 {
+            continue;
+        }
+
+        // v = x - alpha e1, with alpha's sign chosen to avoid cancellation
+        let alpha = -norm.copysign(a.get(k + 1, k));
+        let mut v: Vec<f64> = (0..n)
+            .map(|i| if i > k { a.get(i, k) } else { 0.0 })
+            .collect();
+        v[k + 1] -= alpha;
+        let v_norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        v.iter_mut().for_each(|x| *x /= v_norm);
+
+        // A <- H A H with H = I - 2 v v^T, as A - 2 (v w^T + w v^T)
+        let p: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| a.get(i, j) * v[j]).sum())
+            .collect();
+        let kk: f64 = v.iter().zip(&p).map(|(x, y)| x * y).sum();
+        let w: Vec<f64> = p.iter().zip(&v).map(|(pi, vi)| pi - kk * vi).collect();
+        for i in 0..n {
+            for j in 0..n {
+                let value = a.get(i, j) - 2.0 * (v[i] * w[j] + w[i] * v[j]);
+                a.set(i, j, value);
+            }
+        }
+
+        // Q <- Q H
+        for i in 0..n {
+            let qv: f64 = (0..n).map(|j| q.get(i, j) * v[j]).sum();
+            for (j, vj) in v.iter().enumerate() {
+                q.set(i, j, q.get(i, j) - 2.0 * qv * vj);
+            }
+        }
+    }
+
+    let diag = (0..n).map(|i| a.get(i, i)).collect();
+    let off_diag = (1..n).map(|i| a.get(i, i - 1)).collect();
+
+    Ok((diag, off_diag, q))
+}
+
+/// Cosine and sine of the rotation `[c s; -s c]` that maps `(a, b)` to
+/// `(r, 0)` with `r = hypot(a, b)`.
+pub fn givens(a: f64, b: f64) -> (f64, f64) {
+    if b == 0.0 {
+        return (1.0, 0.0);
+    }
+    let r = a.hypot(b);
+    (a / r, b / r)
+}
+
+/// Applies the rotation from `givens` to rows `i` and `k` of `m` in place,
+/// so that `(m[i][j], m[k][j])` becomes `(c x + s y, c y - s x)`.
+pub fn apply_givens(m: &mut Matrix, i: usize, k: usize, c: f64, s: f64) {
+    assert!(i < m.rows && k < m.rows, "Row index out of bounds");
+    assert!(i != k, "Givens rotation needs two distinct rows");
+
+    for j in 0..m.cols {
+        let x = m.data[i * m.cols + j];
+        let y = m.data[k * m.cols + j];
+        m.data[i * m.cols + j] = c * x + s * y;
+        m.data[k * m.cols + j] = c * y - s * x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        assert_eq!(got.len(), want.len());
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-10, "got {got:?}, want {want:?}");
+        }
+    }
+
+    #[test]
+    fn test_plu_reconstructs_permuted_matrix() {
+        use crate::math::matrix::ops::mul;
+
+        // a zero leading entry forces a swap; plain `lu` gives up on it
+        let a = Matrix::new(3, 3, vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0]);
+        assert!(matches!(lu(&a), Err(Error::SingularMatrix)));
+
+        let (perm, lower, upper, swaps) = plu(&a).unwrap();
+        assert_eq!(perm, vec![2, 0, 1]);
+        assert_eq!(swaps, 2);
+
+        let mut permuted = Matrix::zeros(3, 3);
+        for (i, &p) in perm.iter().enumerate() {
+            for j in 0..3 {
+                permuted.set(i, j, a.get(p, j));
+            }
+        }
+        assert!(mul(&lower, &upper).unwrap().approx_eq(&permuted, 1e-12));
+        for i in 0..3 {
+            assert_eq!(lower.get(i, i), 1.0);
+            assert!((i + 1..3).all(|j| lower.get(i, j) == 0.0 && upper.get(j, i) == 0.0));
+        }
+
+        assert!(matches!(
+            plu(&Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 4.0])),
+            Err(Error::SingularMatrix)
+        ));
+    }
+
+    #[test]
+    fn test_to_tridiagonal_reconstructs() {
+        use crate::math::matrix::ops::{mul, transpose};
+
+        let a = Matrix::new(
+            4,
+            4,
+            vec![
+                4.0, 1.0, -2.0, 2.0, //
+                1.0, 2.0, 0.0, 1.0, //
+                -2.0, 0.0, 3.0, -2.0, //
+                2.0, 1.0, -2.0, -1.0,
+            ],
+        );
+        let (diag, off_diag, q) = to_tridiagonal(&a).unwrap();
+        assert_eq!((diag.len(), off_diag.len()), (4, 3));
+
+        let qtq = mul(&transpose(&q), &q).unwrap();
+        assert!(qtq.approx_eq(&Matrix::identity(4), 1e-12));
+
+        let mut t = Matrix::zeros(4, 4);
+        for i in 0..4 {
+            t.set(i, i, diag[i]);
+            if i > 0 {
+                t.set(i, i - 1, off_diag[i - 1]);
+                t.set(i - 1, i, off_diag[i - 1]);
+            }
+        }
+        let rebuilt = mul(&mul(&q, &t).unwrap(), &transpose(&q)).unwrap();
+        assert!(rebuilt.approx_eq(&a, 1e-12));
+
+        // similar matrices share eigenvalues: trace is preserved
+        let trace: f64 = diag.iter().sum();
+        assert!((trace - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_to_tridiagonal_rejects_non_square() {
+        assert!(matches!(
+            to_tridiagonal(&Matrix::zeros(2, 3)),
+            Err(Error::MatrixNotSquare)
+        ));
+    }
+
+    #[test]
+    fn test_tridiagonal_eigenvalues_laplacian() {
+        // tridiag(-1, 2, -1) of size n has eigenvalues 2 - 2 cos(k pi / (n + 1))
+        let n = 8;
+        let got = tridiagonal_eigenvalues(&vec![2.0; n], &vec![-1.0; n - 1]);
+        let want: Vec<f64> = (1..=n)
+            .map(|k| 2.0 - 2.0 * (k as f64 * std::f64::consts::PI / (n + 1) as f64).cos())
+            .collect();
+
+        assert_close(&got, &want);
+    }
+
+    #[test]
+    fn test_tridiagonal_eigenvalues_small() {
+        // [[2, 1, 0], [1, 2, 1], [0, 1, 2]] -> 2 - sqrt(2), 2, 2 + sqrt(2)
+        let got = tridiagonal_eigenvalues(&[2.0, 2.0, 2.0], &[1.0, 1.0]);
+        assert_close(&got, &[2.0 - 2f64.sqrt(), 2.0, 2.0 + 2f64.sqrt()]);
+
+        // [[1, 2], [2, 1]] -> -1, 3
+        assert_close(&tridiagonal_eigenvalues(&[1.0, 1.0], &[2.0]), &[-1.0, 3.0]);
+
+        // already diagonal
+        assert_close(
+            &tridiagonal_eigenvalues(&[3.0, -1.0, 5.0], &[0.0, 0.0]),
+            &[-1.0, 3.0, 5.0],
+        );
+        assert_close(&tridiagonal_eigenvalues(&[4.0], &[]), &[4.0]);
+    }
+
+    #[test]
+    fn test_givens_zeros_target_and_keeps_norm() {
+        let (c, s) = givens(3.0, 4.0);
+        assert_close(&[c, s], &[0.6, 0.8]);
+        assert_eq!(givens(-2.0, 0.0), (1.0, 0.0));
+
+        let mut v = Matrix::new(4, 1, vec![1.0, -3.0, 2.0, 4.0]);
+        let (c, s) = givens(v.get(1, 0), v.get(3, 0));
+        apply_givens(&mut v, 1, 3, c, s);
+        assert_close(&v.data, &[1.0, 5.0, 2.0, 0.0]);
+        assert!((c * c + s * s - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_givens_on_matrix_rows() {
+        // zero the (2, 0) entry against the (0, 0) pivot
+        let mut a = Matrix::new(3, 2, vec![1.0, 2.0, 5.0, 6.0, 1.0, -1.0]);
+        let norms = |m: &Matrix| -> Vec<f64> {
+            (0..m.cols)
+                .map(|j| m.col_iter(j).map(|x| x * x).sum::<f64>().sqrt())
+                .collect()
+        };
+        let before = norms(&a);
+
+        let (c, s) = givens(a.get(0, 0), a.get(2, 0));
+        apply_givens(&mut a, 0, 2, c, s);
+        assert!(a.get(2, 0).abs() < 1e-15);
+        assert!((a.get(0, 0) - 2f64.sqrt()).abs() < 1e-12);
+        assert_eq!(a.row(1), &[5.0, 6.0]);
+        assert_close(&norms(&a), &before);
+    }
+
+    #[test]
+    fn test_power_iteration_dominant_pair() {
+        // eigenvalues 5 and 2, with (1, 1) belonging to 5
+        let a = Matrix::new(2, 2, vec![4.0, 1.0, 2.0, 3.0]);
+        let (lambda, v) = power_iteration(&a, 1000, 1e-10).unwrap();
+        assert!((lambda - 5.0).abs() < 1e-9);
+        assert!((v.get(0, 0) - v.get(1, 0)).abs() < 1e-9);
+        assert!((magnitude(&v) - 1.0).abs() < 1e-12);
+
+        // a negative dominant eigenvalue flips the iterate each step
+        let b = Matrix::new(3, 3, vec![-6.0, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0, 1.0, 2.0]);
+        let (lambda, v) = power_iteration(&b, 1000, 1e-10).unwrap();
+        assert!((lambda + 6.0).abs() < 1e-9);
+        assert!((v.get(0, 0).abs() - 1.0).abs() < 1e-9);
+
+        // the ones vector spans the Laplacian's null space, so starting there
+        // would stop at (0, ones) instead of the dominant pair
+        let laplacian = Matrix::new(2, 2, vec![1.0, -1.0, -1.0, 1.0]);
+        let (lambda, v) = power_iteration(&laplacian, 1000, 1e-10).unwrap();
+        assert!((lambda - 2.0).abs() < 1e-9);
+        let expected = std::f64::consts::FRAC_1_SQRT_2;
+        let sign = v.get(0, 0).signum();
+        assert!((sign * v.get(0, 0) - expected).abs() < 1e-9);
+        assert!((sign * v.get(1, 0) + expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_power_iteration_gives_up() {
+        // a rotation has no real dominant eigenvalue
+        let rotation = Matrix::new(2, 2, vec![0.0, -1.0, 1.0, 0.0]);
+        assert!(power_iteration(&rotation, 500, 1e-8).is_none());
+        assert!(power_iteration(&Matrix::identity(3), 0, 1e-8).is_none());
+        let (lambda, _) = power_iteration(&Matrix::identity(3), 1, 1e-8).unwrap();
+        assert!((lambda - 1.0).abs() < 1e-12);
+    }
 }
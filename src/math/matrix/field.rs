@@ -0,0 +1,217 @@
+//! Scalar abstraction for the elimination and decomposition kernels.
+//!
+//! `Matrix` stores `f64`, but the kernels here work on any row-major slice of
+//! a `Field`, so exact or complex element types can reuse them.
+
+use crate::math::fraction::Fraction;
+use crate::math::matrix::utils::error::Error;
+use num_traits::{One, Zero};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+pub trait Field:
+    Copy
+    + PartialEq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Relative rounding error of one operation; 0 for exact types.
+    const EPSILON: f64;
+
+    /// Size used to rank pivot candidates, like `abs` for reals.
+    fn modulus(&self) -> f64;
+}
+
+impl Field for f64 {
+    const EPSILON: f64 = f64::EPSILON;
+
+    #[inline(always)]
+    fn modulus(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Field for Fraction {
+    const EPSILON: f64 = 0.0;
+
+    fn modulus(&self) -> f64 {
+        self.to_f64().abs()
+    }
+}
+
+/// Gauss-Jordan elimination with partial pivoting on a `rows x cols`
+/// row-major slice, pivoting only in the first `pivot_cols` columns so an
+/// augmented block like `[A | I]` is carried along. Returns the number of
+/// pivots. Entries with modulus at most `max(rows, pivot_cols) * EPSILON *
+/// max|a|` count as zero.
+pub fn gauss_jordan<F: Field>(a: &mut [F], rows: usize, cols: usize, pivot_cols: usize) -> usize {
+    assert_eq!(a.len(), rows * cols, "Data length must match dimensions");
+
+    let max_abs = (0..rows)
+        .flat_map(|i| (0..pivot_cols).map(move |j| i * cols + j))
+        .map(|idx| a[idx].modulus())
+        .fold(0.0, f64::max);
+    let tol = rows.max(pivot_cols) as f64 * F::EPSILON * max_abs;
+
+    let mut rank = 0;
+    for col in 0..pivot_cols {
+        if rank == rows {
+            break;
+        }
+
+        // find pivot row
+        let mut pivot_row = rank;
+        for j in rank + 1..rows {
+            if a[j * cols + col].modulus() > a[pivot_row * cols + col].modulus() {
+                pivot_row = j;
+            }
+        }
+
+        if a[pivot_row * cols + col].modulus() <= tol {
+            // no pivot here; clear the rounding noise below the current row
+            for i in rank..rows {
+                a[i * cols + col] = F::zero();
+            }
+            continue;
+        }
+
+        if pivot_row != rank {
+            for j in 0..cols {
+                a.swap(rank * cols + j, pivot_row * cols + j);
+            }
+        }
+
+        // normalize pivot row
+        let pivot = a[rank * cols + col];
+        for j in 0..cols {
+            a[rank * cols + j] = a[rank * cols + j] / pivot;
+        }
+
+        // eliminate all others
+        for k in 0..rows {
+            if k != rank {
+                let factor = a[k * cols + col];
+                for j in 0..cols {
+                    a[k * cols + j] = a[k * cols + j] - factor * a[rank * cols + j];
+                }
+            }
+        }
+
+        rank += 1;
+    }
+    rank
+}
+
+/// In-place LU factorization with partial pivoting of an `n x n` row-major
+/// slice, `P A = L U`. On return `a` holds `U` on and above the diagonal;
+/// the unit lower factor is returned separately with `(perm, swaps)` as in
+/// `decomp::plu`. A pivot with modulus below `tol` (or exactly zero) makes
+/// the matrix singular.
+pub fn lu_in_place<F: Field>(
+    a: &mut [F],
+    n: usize,
+    tol: f64,
+) -> Result<(Vec<usize>, Vec<F>, usize), Error> {
+    assert_eq!(a.len(), n * n, "Data length must match dimensions");
+
+    let mut perm: Vec<usize> = (0..n).collect();
+    let mut lower = vec![F::zero(); n * n];
+    for i in 0..n {
+        lower[i * n + i] = F::one();
+    }
+    let mut swaps = 0;
+
+    for i in 0..n {
+        let mut pivot_row = i;
+        for j in i + 1..n {
+            if a[j * n + i].modulus() > a[pivot_row * n + i].modulus() {
+                pivot_row = j;
+            }
+        }
+
+        let pivot = a[pivot_row * n + i];
+        if pivot.modulus() < tol || pivot.is_zero() {
+            return Err(Error::SingularMatrix);
+        }
+
+        if pivot_row != i {
+            perm.swap(i, pivot_row);
+            for k in 0..n {
+                a.swap(i * n + k, pivot_row * n + k);
+            }
+            // the multipliers already in L move with their rows
+            for k in 0..i {
+                lower.swap(i * n + k, pivot_row * n + k);
+            }
+            swaps += 1;
+        }
+
+        for j in (i + 1)..n {
+            let factor = a[j * n + i] / a[i * n + i];
+            lower[j * n + i] = factor;
+
+            for k in i..n {
+                a[j * n + k] = a[j * n + k] - factor * a[i * n + k];
+            }
+        }
+    }
+
+    Ok((perm, lower, swaps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::decomp::plu;
+    use crate::math::matrix::matrix::Matrix;
+    use crate::math::matrix::ops::{inv, rref};
+    use crate::math::random::Rng;
+
+    #[test]
+    fn test_f64_paths_through_trait() {
+        use crate::math::matrix::ops::mul;
+
+        // inv, rref and plu all run on the generic kernels for f64 matrices
+        let mut rng = Rng::new(9);
+        for _ in 0..100 {
+            let data = (0..16).map(|_| rng.next_f64() * 10.0 - 5.0).collect();
+            let a = Matrix::new(4, 4, data);
+
+            assert!(rref(&a).approx_eq(&Matrix::identity(4), 1e-12));
+            let product = mul(&inv(&a).unwrap(), &a).unwrap();
+            assert!(product.approx_eq(&Matrix::identity(4), 1e-4));
+
+            let (perm, lower, upper, _) = plu(&a).unwrap();
+            let mut permuted = Matrix::zeros(4, 4);
+            for (i, &p) in perm.iter().enumerate() {
+                for j in 0..4 {
+                    permuted.set(i, j, a.get(p, j));
+                }
+            }
+            assert!(mul(&lower, &upper).unwrap().approx_eq(&permuted, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_gauss_jordan_augmented_inverse() {
+        // [A | I] -> [I | A^-1]
+        #[rustfmt::skip]
+        let mut aug = vec![
+            2.0, 1.0, 1.0, 0.0,
+            1.0, 1.0, 0.0, 1.0,
+        ];
+        assert_eq!(gauss_jordan(&mut aug, 2, 4, 2), 2);
+        assert_eq!(aug, vec![1.0, 0.0, 1.0, -1.0, 0.0, 1.0, -1.0, 2.0]);
+
+        let mut singular = vec![1.0, 2.0, 2.0, 4.0];
+        assert_eq!(gauss_jordan(&mut singular, 2, 2, 2), 1);
+        assert!(matches!(
+            lu_in_place(&mut [0.0, 0.0, 0.0, 1.0], 2, 0.0),
+            Err(Error::SingularMatrix)
+        ));
+    }
+}
@@ -1,4 +1,8 @@
+use crate::math::matrix::utils::error::Error;
 use std::fmt;
+use std::io::{self, Read, Write};
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix {
@@ -32,6 +36,57 @@ impl Matrix {
         }
     }
 
+    /// The `n x n` Hilbert matrix, `1 / (i + j + 1)`; badly conditioned even
+    /// for small `n`.
+    pub fn hilbert(n: usize) -> Self {
+        let mut result = Self::zeros(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                result.set(i, j, 1.0 / (i + j + 1) as f64);
+            }
+        }
+        result
+    }
+
+    /// One row `[1, x, x^2, ..., x^degree]` per point, matching the low-degree
+    /// first coefficient order of `Polynomial`.
+    pub fn vandermonde(points: &[f64], degree: usize) -> Self {
+        let mut data = Vec::with_capacity(points.len() * (degree + 1));
+        for &x in points {
+            let mut power = 1.0;
+            for _ in 0..=degree {
+                data.push(power);
+                power *= x;
+            }
+        }
+        Self::new(points.len(), degree + 1, data)
+    }
+
+    /// Stacks equal-length rows; ragged input is a `MatrixSizeMismatch`.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Matrix, Error> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(Error::MatrixSizeMismatch);
+        }
+        Ok(Matrix::new(rows.len(), cols, rows.concat()))
+    }
+
+    /// Like `from_rows`, but each inner `Vec` is a column.
+    pub fn from_cols(cols: &[Vec<f64>]) -> Result<Matrix, Error> {
+        let rows = cols.first().map_or(0, Vec::len);
+        if cols.iter().any(|col| col.len() != rows) {
+            return Err(Error::MatrixSizeMismatch);
+        }
+
+        let mut result = Matrix::zeros(rows, cols.len());
+        for (j, col) in cols.iter().enumerate() {
+            for (i, &value) in col.iter().enumerate() {
+                result.set(i, j, value);
+            }
+        }
+        Ok(result)
+    }
+
     pub fn get(&self, row: usize, col: usize) -> f64 {
         self.data[row * self.cols + col]
     }
@@ -40,6 +95,84 @@ impl Matrix {
         self.data[row * self.cols + col] = value;
     }
 
+    pub fn row(&self, i: usize) -> &[f64] {
+        assert!(i < self.rows, "Row index out of bounds");
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Rows as slices, top to bottom.
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[f64]> {
+        // not `chunks`, which rejects a width of zero
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    /// Entries of column `j`, top to bottom.
+    pub fn col_iter(&self, j: usize) -> impl Iterator<Item = f64> + '_ {
+        assert!(j < self.cols, "Column index out of bounds");
+        self.data.iter().skip(j).step_by(self.cols).copied()
+    }
+
+    // elementary row operations
+
+    pub fn scale_row(&mut self, i: usize, factor: f64) {
+        assert!(i < self.rows, "Row index out of bounds");
+        for x in &mut self.data[i * self.cols..(i + 1) * self.cols] {
+            *x *= factor;
+        }
+    }
+
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        assert!(i < self.rows && j < self.rows, "Row index out of bounds");
+        for k in 0..self.cols {
+            self.data.swap(i * self.cols + k, j * self.cols + k);
+        }
+    }
+
+    /// `row[dst] += factor * row[src]`.
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, factor: f64) {
+        assert!(
+            dst < self.rows && src < self.rows,
+            "Row index out of bounds"
+        );
+        for k in 0..self.cols {
+            let value = self.data[src * self.cols + k];
+            self.data[dst * self.cols + k] += factor * value;
+        }
+    }
+
+    /// `self += alpha * u vᵀ` in place, for vectors with `rows` and `cols`
+    /// entries.
+    pub fn rank1_update(&mut self, alpha: f64, u: &Matrix, v: &Matrix) {
+        assert!(
+            (u.rows == 1 || u.cols == 1) && (v.rows == 1 || v.cols == 1),
+            "Rank-1 update requires 1xN or Nx1 vectors"
+        );
+        assert!(
+            u.data.len() == self.rows && v.data.len() == self.cols,
+            "Vector sizes must match matrix dimensions"
+        );
+
+        for i in 0..self.rows {
+            let scale = alpha * u.data[i];
+            for j in 0..self.cols {
+                self.data[i * self.cols + j] += scale * v.data[j];
+            }
+        }
+    }
+
+    /// Same data read with new dimensions; the element count must not change.
+    pub fn reshape(&self, rows: usize, cols: usize) -> Result<Matrix, Error> {
+        if rows.checked_mul(cols) != Some(self.data.len()) {
+            return Err(Error::MatrixSizeMismatch);
+        }
+        Ok(Matrix::new(rows, cols, self.data.clone()))
+    }
+
+    /// All entries in row-major order as a 1xN row vector.
+    pub fn flatten(&self) -> Matrix {
+        Matrix::new(1, self.data.len(), self.data.clone())
+    }
+
     pub fn minor(&self, row: usize, col: usize) -> Matrix {
         let mut data = Vec::new();
         for r in 0..self.rows {
@@ -55,16 +188,404 @@ impl Matrix {
         }
         Matrix::new(self.rows - 1, self.cols - 1, data)
     }
+
+    /// True when the dimensions match and every pair of entries differs by
+    /// less than `tol`. NaN entries never compare equal.
+    pub fn approx_eq(&self, other: &Matrix, tol: f64) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|(a, b)| (a - b).abs() < tol)
+    }
+}
+
+// .npy v1.0 I/O, little-endian f64 only
+impl Matrix {
+    /// Reads a 2-D (or 1-D, as a column) little-endian float64 `.npy` v1.0
+    /// array. Fortran-order arrays are transposed into row-major storage.
+    pub fn read_npy(r: &mut impl Read) -> io::Result<Matrix> {
+        let mut prefix = [0u8; 10];
+        r.read_exact(&mut prefix)?;
+        if &prefix[..6] != NPY_MAGIC || prefix[6] != 1 {
+            return Err(invalid_npy("not a version 1 .npy file"));
+        }
+
+        let header_len = u16::from_le_bytes([prefix[8], prefix[9]]) as usize;
+        let mut header = vec![0u8; header_len];
+        r.read_exact(&mut header)?;
+        let header = String::from_utf8(header).map_err(|_| invalid_npy("header is not UTF-8"))?;
+
+        if npy_field(&header, "descr") != Some("'<f8'") {
+            return Err(invalid_npy(
+                "only little-endian float64 ('<f8') is supported",
+            ));
+        }
+        let fortran = match npy_field(&header, "fortran_order") {
+            Some("False") => false,
+            Some("True") => true,
+            _ => return Err(invalid_npy("missing fortran_order")),
+        };
+        let shape = npy_field(&header, "shape")
+            .and_then(|v| v.strip_prefix('('))
+            .and_then(|v| v.strip_suffix(')'))
+            .ok_or_else(|| invalid_npy("missing shape"))?;
+        let dims = shape
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.parse::<usize>().map_err(|_| invalid_npy("bad shape")))
+            .collect::<io::Result<Vec<usize>>>()?;
+        let (rows, cols) = match dims[..] {
+            [n] => (n, 1),
+            [rows, cols] => (rows, cols),
+            _ => return Err(invalid_npy("only 1-D and 2-D arrays are supported")),
+        };
+
+        let byte_len = rows
+            .checked_mul(cols)
+            .and_then(|len| len.checked_mul(8))
+            .ok_or_else(|| invalid_npy("shape too large"))?;
+        // grow with the data actually present rather than trusting the header
+        let mut bytes = Vec::new();
+        r.take(byte_len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != byte_len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "npy data shorter than its shape",
+            ));
+        }
+        let values = bytes
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()));
+
+        let mut matrix = Matrix::zeros(rows, cols);
+        if fortran {
+            for (k, v) in values.enumerate() {
+                matrix.set(k % rows, k / rows, v);
+            }
+        } else {
+            for (slot, v) in matrix.data.iter_mut().zip(values) {
+                *slot = v;
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Writes the matrix as a C-order little-endian float64 `.npy` v1.0 array.
+    pub fn write_npy(&self, w: &mut impl Write) -> io::Result<()> {
+        let mut header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+            self.rows, self.cols
+        );
+        // pad with spaces so the data starts on a 64-byte boundary
+        let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+        header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+        header.push('\n');
+
+        let header_len = u16::try_from(header.len()).map_err(|_| invalid_npy("header too long"))?;
+        w.write_all(NPY_MAGIC)?;
+        w.write_all(&[1, 0])?;
+        w.write_all(&header_len.to_le_bytes())?;
+        w.write_all(header.as_bytes())?;
+        for v in &self.data {
+            w.write_all(&v.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_npy(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+// the raw value of `'key': value` in an .npy header dict
+fn npy_field<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}':"))? + key.len() + 3;
+    let rest = header[start..].trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')')? + 1
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(rest[..end].trim())
+}
+
+impl Matrix {
+    /// Every entry to `precision` decimal places, one row per line, with
+    /// each column right-aligned to its widest entry. `Display` does the same
+    /// when given a precision, as in `{:.3}`.
+    pub fn format(&self, precision: usize) -> String {
+        format!("{self:.precision$}")
+    }
 }
 
 impl fmt::Display for Matrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(precision) = f.precision() {
+            let cells: Vec<String> = self
+                .data
+                .iter()
+                .map(|x| format!("{x:.precision$}"))
+                .collect();
+            let widths: Vec<usize> = (0..self.cols)
+                .map(|j| {
+                    (0..self.rows)
+                        .map(|i| cells[i * self.cols + j].len())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+
+            for row in cells.chunks(self.cols.max(1)) {
+                let line: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{cell:>width$}"))
+                    .collect();
+                writeln!(f, "{}", line.join(" "))?;
+            }
+            return Ok(());
+        }
+
         for i in 0..self.rows {
             for j in 0..self.cols {
                 write!(f, "{} ", self.data[i * self.cols + j])?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_and_column_iterators() {
+        let m = Matrix::new(3, 3, (1..=9).map(f64::from).collect());
+
+        let rows: Vec<&[f64]> = m.rows_iter().collect();
+        assert_eq!(rows, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(m.row(1), &[4.0, 5.0, 6.0]);
+
+        let cols: Vec<Vec<f64>> = (0..3).map(|j| m.col_iter(j).collect()).collect();
+        assert_eq!(cols, [[1.0, 4.0, 7.0], [2.0, 5.0, 8.0], [3.0, 6.0, 9.0]]);
+
+        let wide = Matrix::zeros(2, 0);
+        assert_eq!(wide.rows_iter().count(), 2);
+        assert!(wide.rows_iter().all(<[f64]>::is_empty));
+    }
+
+    #[test]
+    #[should_panic(expected = "Column index out of bounds")]
+    fn test_col_iter_out_of_bounds() {
+        let _ = Matrix::zeros(2, 2).col_iter(2);
+    }
+
+    #[test]
+    fn test_from_rows_and_cols() {
+        let rows = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let m = Matrix::from_rows(&rows).unwrap();
+        assert_eq!(m, Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+
+        let cols = vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]];
+        assert_eq!(Matrix::from_cols(&cols).unwrap(), m);
+
+        let empty = Matrix::from_rows(&[]).unwrap();
+        assert_eq!((empty.rows, empty.cols), (0, 0));
+        let blank_rows = Matrix::from_rows(&[vec![], vec![]]).unwrap();
+        assert_eq!((blank_rows.rows, blank_rows.cols), (2, 0));
+    }
+
+    #[test]
+    fn test_from_ragged_input() {
+        let ragged = vec![vec![1.0, 2.0], vec![3.0]];
+        assert!(matches!(
+            Matrix::from_rows(&ragged),
+            Err(Error::MatrixSizeMismatch)
+        ));
+        assert!(matches!(
+            Matrix::from_cols(&ragged),
+            Err(Error::MatrixSizeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_hilbert() {
+        let h = Matrix::hilbert(3);
+
+        assert_eq!((h.rows, h.cols), (3, 3));
+        assert_eq!(h.get(0, 0), 1.0);
+        assert_eq!(h.get(0, 2), 1.0 / 3.0);
+        assert_eq!(h.get(2, 2), 1.0 / 5.0);
+        assert_eq!(h.get(1, 2), h.get(2, 1));
+    }
+
+    #[test]
+    fn test_vandermonde() {
+        let v = Matrix::vandermonde(&[2.0, -1.0, 0.0], 3);
+
+        assert_eq!((v.rows, v.cols), (3, 4));
+        assert_eq!(v.row(0), &[1.0, 2.0, 4.0, 8.0]);
+        assert_eq!(v.row(1), &[1.0, -1.0, 1.0, -1.0]);
+        assert_eq!(v.row(2), &[1.0, 0.0, 0.0, 0.0]);
+
+        // rows dotted with coefficients evaluate the polynomial
+        use crate::math::polynomial::Polynomial;
+        let p = Polynomial::new(vec![1.0, -3.0, 0.5, 2.0]);
+        for (i, &x) in [2.0, -1.0, 0.0].iter().enumerate() {
+            let dot: f64 = v.row(i).iter().zip(&p.coeffs).map(|(a, b)| a * b).sum();
+            assert_eq!(dot, p.eval(x));
+        }
+    }
+
+    #[test]
+    fn test_elementary_row_ops() {
+        let mut m = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        m.scale_row(1, -2.0);
+        assert_eq!(m.data, vec![1.0, 2.0, -6.0, -8.0, 5.0, 6.0]);
+
+        m.swap_rows(0, 2);
+        assert_eq!(m.data, vec![5.0, 6.0, -6.0, -8.0, 1.0, 2.0]);
+        m.swap_rows(1, 1);
+        assert_eq!(m.data, vec![5.0, 6.0, -6.0, -8.0, 1.0, 2.0]);
+
+        m.add_scaled_row(0, 2, -5.0);
+        assert_eq!(m.data, vec![0.0, -4.0, -6.0, -8.0, 1.0, 2.0]);
+
+        // a row added to itself doubles
+        m.add_scaled_row(2, 2, 1.0);
+        assert_eq!(m.row(2), &[2.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row index out of bounds")]
+    fn test_row_op_out_of_bounds() {
+        Matrix::zeros(2, 2).add_scaled_row(0, 2, 1.0);
+    }
+
+    #[test]
+    fn test_reshape_and_flatten() {
+        let m = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let reshaped = m.reshape(3, 2).unwrap();
+        assert_eq!((reshaped.rows, reshaped.cols), (3, 2));
+        assert_eq!(reshaped.row(1), &[3.0, 4.0]);
+        assert_eq!(reshaped.reshape(2, 3).unwrap(), m);
+
+        assert!(matches!(m.reshape(4, 2), Err(Error::MatrixSizeMismatch)));
+        assert!(matches!(
+            m.reshape(usize::MAX, 2),
+            Err(Error::MatrixSizeMismatch)
+        ));
+
+        let flat = m.flatten();
+        assert_eq!((flat.rows, flat.cols), (1, 6));
+        assert_eq!(flat.data, m.data);
+    }
+
+    #[test]
+    fn test_format_precision() {
+        let m = Matrix::new(2, 3, vec![1.0 / 3.0, -12.5, 0.005, 2.0 / 3.0, 100.0, 1e-9]);
+        let expected = "0.33 -12.50 0.01\n0.67 100.00 0.00\n";
+
+        assert_eq!(m.format(2), expected);
+        assert_eq!(format!("{m:.2}"), expected);
+        assert_eq!(Matrix::new(1, 2, vec![0.5, 2.0]).format(0), "0 2\n");
+        assert_eq!(Matrix::zeros(0, 0).format(3), "");
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let m = Matrix::new(2, 2, vec![1.0, -2.0, 3.5, 1e6]);
+        let mut perturbed = m.clone();
+        perturbed.set(1, 1, 1e6 + 1e-7);
+        perturbed.set(0, 1, -2.0 - 5e-8);
+
+        assert!(m.approx_eq(&perturbed, 1e-6));
+        assert!(!m.approx_eq(&perturbed, 1e-8));
+        assert!(m.approx_eq(&m, f64::MIN_POSITIVE));
+
+        // same data, different shape
+        assert!(!m.approx_eq(&Matrix::new(1, 4, m.data.clone()), 1.0));
+
+        perturbed.set(0, 0, f64::NAN);
+        assert!(!perturbed.approx_eq(&perturbed, 1.0));
+    }
+
+    #[test]
+    fn test_npy_roundtrip() {
+        let m = Matrix::new(2, 3, vec![1.0, -2.5, 3.0, 4.25, f64::MAX, 0.0]);
+        let mut buf = Vec::new();
+        m.write_npy(&mut buf).unwrap();
+
+        assert_eq!(&buf[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(buf.len(), 10 + header_len + 6 * 8);
+
+        let header = std::str::from_utf8(&buf[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }"));
+        assert!(header.ends_with('\n'));
+
+        assert_eq!(Matrix::read_npy(&mut buf.as_slice()).unwrap(), m);
+    }
+
+    #[test]
+    fn test_npy_read_numpy_variants() {
+        // 1-D and Fortran-order arrays as numpy writes them
+        let encode = |header: &str, values: &[f64]| {
+            let mut buf = b"\x93NUMPY\x01\x00".to_vec();
+            buf.extend((header.len() as u16).to_le_bytes());
+            buf.extend(header.as_bytes());
+            for v in values {
+                buf.extend(v.to_le_bytes());
+            }
+            buf
+        };
+
+        let vector = encode(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }\n",
+            &[1.0, 2.0, 3.0],
+        );
+        let m = Matrix::read_npy(&mut vector.as_slice()).unwrap();
+        assert_eq!((m.rows, m.cols, m.data), (3, 1, vec![1.0, 2.0, 3.0]));
+
+        let fortran = encode(
+            "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 2), }\n",
+            &[1.0, 3.0, 2.0, 4.0],
+        );
+        let m = Matrix::read_npy(&mut fortran.as_slice()).unwrap();
+        assert_eq!(m.data, vec![1.0, 2.0, 3.0, 4.0]);
+
+        let ints = encode(
+            "{'descr': '<i8', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0.0],
+        );
+        let err = Matrix::read_npy(&mut ints.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let truncated = &vector[..vector.len() - 1];
+        let err = Matrix::read_npy(&mut &truncated[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // a lying header must fail cleanly instead of allocating its shape
+        let huge = encode(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (4000000000, 4000000000), }\n",
+            &[1.0],
+        );
+        let err = Matrix::read_npy(&mut huge.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let big = encode(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (100000, 100000), }\n",
+            &[1.0],
+        );
+        let err = Matrix::read_npy(&mut big.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
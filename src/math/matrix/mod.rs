@@ -1,3 +1,9 @@
+pub mod complex_matrix;
+pub mod csv;
 pub mod decomp;
+pub mod field;
+#[allow(clippy::module_inception)]
 pub mod matrix;
 pub mod ops;
+pub mod sparse;
+pub mod utils;
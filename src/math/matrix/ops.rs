@@ -1,16 +1,16 @@
-use crate::matrix::matrix::Matrix;
-use crate::utils::error::Error;
+use crate::math::fraction::Fraction;
+use crate::math::matrix::decomp::plu;
+use crate::math::matrix::field::gauss_jordan;
+use crate::math::matrix::matrix::Matrix;
+use crate::math::matrix::utils::error::Error;
+use crate::math::vectorized::{add_slices, offset_slice, scale_slice, sub_slices};
 
 pub fn add(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
     if a.rows != b.rows || a.cols != b.cols {
         return Err(Error::MatrixSizeMismatch);
     }
     let mut result = Matrix::zeros(a.rows, a.cols);
-    for i in 0..a.rows {
-        for j in 0..a.cols {
-            result.set(i, j, a.get(i, j) + b.get(i, j));
-        }
-    }
+    add_slices(&a.data, &b.data, &mut result.data);
     Ok(result)
 }
 
@@ -32,16 +32,30 @@ pub fn mul(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
     Ok(result)
 }
 
-pub fn sub(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
-    if a.rows != b.rows || a.cols != b.cols {
+/// `tr(a b)` summed straight from the entries, without forming the product.
+pub fn trace_of_product(a: &Matrix, b: &Matrix) -> Result<f64, Error> {
+    if a.cols != b.rows {
         return Err(Error::MatrixSizeMismatch);
     }
-    let mut result = Matrix::zeros(a.rows, a.cols);
+    if a.rows != b.cols {
+        return Err(Error::MatrixNotSquare);
+    }
+
+    let mut sum = 0.0;
     for i in 0..a.rows {
-        for j in 0..a.cols {
-            result.set(i, j, a.get(i, j) - b.get(i, j));
+        for k in 0..a.cols {
+            sum += a.get(i, k) * b.get(k, i);
         }
     }
+    Ok(sum)
+}
+
+pub fn sub(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+    if a.rows != b.rows || a.cols != b.cols {
+        return Err(Error::MatrixSizeMismatch);
+    }
+    let mut result = Matrix::zeros(a.rows, a.cols);
+    sub_slices(&a.data, &b.data, &mut result.data);
     Ok(result)
 }
 
@@ -70,24 +84,30 @@ pub fn transpose(matrix: &Matrix) -> Matrix {
 
 pub fn scalar_mul(matrix: &Matrix, scalar: f64) -> Matrix {
     let mut result = Matrix::zeros(matrix.rows, matrix.cols);
-    for i in 0..matrix.rows {
-        for j in 0..matrix.cols {
-            result.set(i, j, matrix.get(i, j) * scalar);
-        }
-    }
+    scale_slice(&matrix.data, scalar, &mut result.data);
     result
 }
 
-pub fn power(matrix: &Matrix, scalar: f64) -> Matrix {
+/// Adds `scalar` to every element.
+pub fn scalar_add(matrix: &Matrix, scalar: f64) -> Matrix {
     let mut result = Matrix::zeros(matrix.rows, matrix.cols);
-    for i in 0..matrix.rows {
-        for j in 0..matrix.cols {
-            result.set(i, j, matrix.get(i, j).powf(scalar));
-        }
-    }
+    offset_slice(&matrix.data, scalar, &mut result.data);
     result
 }
 
+/// Applies `f` to every element. No SIMD here, since `f` is arbitrary.
+pub fn map(matrix: &Matrix, f: impl Fn(f64) -> f64) -> Matrix {
+    Matrix::new(
+        matrix.rows,
+        matrix.cols,
+        matrix.data.iter().map(|&x| f(x)).collect(),
+    )
+}
+
+pub fn power(matrix: &Matrix, scalar: f64) -> Matrix {
+    map(matrix, |x| x.powf(scalar))
+}
+
 pub fn determinant(matrix: &Matrix) -> f64 {
     if matrix.rows != matrix.cols {
         panic!("Matrix must be square");
@@ -113,6 +133,24 @@ pub fn determinant(matrix: &Matrix) -> f64 {
     det
 }
 
+/// Sign of the determinant (-1, 0 or 1) from the pivots of a partial-pivoting
+/// LU factorization. Numerically singular matrices give 0.
+pub fn det_sign(matrix: &Matrix) -> i32 {
+    assert_eq!(matrix.rows, matrix.cols, "Matrix must be square");
+
+    match plu(matrix) {
+        Ok((_, _, upper, swaps)) => {
+            let negative_pivots = (0..upper.rows).filter(|&i| upper.get(i, i) < 0.0).count();
+            if (swaps + negative_pivots) % 2 == 0 {
+                1
+            } else {
+                -1
+            }
+        }
+        Err(_) => 0,
+    }
+}
+
 pub fn eigenvalues(matrix: &Matrix) -> Vec<f64> {
     if matrix.rows != matrix.cols {
         panic!("Matrix must be square");
@@ -161,48 +199,9 @@ pub fn inv(matrix: &Matrix) -> Option<Matrix> {
         augmented.data[i * augmented.cols + (i + n)] = 1.0; // identity matrix here bc using the function was a wast of space!
     }
 
-    // Gauss-Jordan elim
-    for i in 0..n {
-        // find pivot row
-        let mut pivot_row = i;
-        for j in i + 1..n {
-            if augmented.data[j * augmented.cols + i].abs()
-                > augmented.data[pivot_row * augmented.cols + i].abs()
-            {
-                pivot_row = j;
-            }
-        }
-
-        // swap rows if necessary
-        if pivot_row != i {
-            for j in 0..2 * n {
-                augmented
-                    .data
-                    .swap(i * augmented.cols + j, pivot_row * augmented.cols + j);
-            }
-        }
-
-        // check for singular matrix
-        if augmented.data[i * augmented.cols + i] == 0.0 {
-            return None; // matrix is singular, no inverse exists
-        }
-
-        // normalize pivot row
-        let pivot = augmented.data[i * augmented.cols + i];
-        for j in 0..2 * n {
-            augmented.data[i * augmented.cols + j] /= pivot;
-        }
-
-        // eliminate all others
-        for k in 0..n {
-            if k != i {
-                let factor = augmented.data[k * augmented.cols + i];
-                for j in 0..2 * n {
-                    augmented.data[k * augmented.cols + j] -=
-                        factor * augmented.data[i * augmented.cols + j];
-                }
-            }
-        }
+    // Gauss-Jordan elim on the left half; full rank there means invertible
+    if eliminate(&mut augmented, n) < n {
+        return None; // matrix is singular, no inverse exists
     }
 
     // extract inverse matrix from the aug matrix
@@ -222,6 +221,122 @@ pub fn inv(matrix: &Matrix) -> Option<Matrix> {
     })
 }
 
+// integer entries of a square matrix, widened so Bareiss products don't overflow
+fn integer_entries(matrix: &Matrix) -> Vec<i128> {
+    assert_eq!(matrix.rows, matrix.cols, "Matrix must be square");
+
+    matrix
+        .data
+        .iter()
+        .map(|&x| {
+            assert!(
+                x.fract() == 0.0 && x.abs() <= i64::MAX as f64,
+                "Matrix entries must be integers"
+            );
+            x as i128
+        })
+        .collect()
+}
+
+// Fraction-free Gauss-Jordan (Bareiss) on the row-major `rows x cols` block,
+// pivoting in the first `n` columns. Every division is exact, and on success
+// the left block becomes `d * I` where `d` is the final pivot. Returns `d`
+// and the number of row swaps, or `None` if the left block is singular.
+fn bareiss(a: &mut [i128], n: usize, cols: usize) -> Option<(i128, usize)> {
+    let mut prev = 1;
+    let mut swaps = 0;
+
+    for k in 0..n {
+        let pivot_row = (k..n).find(|&i| a[i * cols + k] != 0)?;
+        if pivot_row != k {
+            for j in 0..cols {
+                a.swap(k * cols + j, pivot_row * cols + j);
+            }
+            swaps += 1;
+        }
+
+        let pivot = a[k * cols + k];
+        for i in (0..n).filter(|&i| i != k) {
+            let factor = a[i * cols + k];
+            for j in 0..cols {
+                let value = pivot
+                    .checked_mul(a[i * cols + j])
+                    .zip(factor.checked_mul(a[k * cols + j]))
+                    .and_then(|(x, y)| x.checked_sub(y))
+                    .expect("Matrix entries too large for exact elimination");
+                a[i * cols + j] = value / prev;
+            }
+        }
+        prev = pivot;
+    }
+
+    Some((prev, swaps))
+}
+
+/// Exact determinant of a matrix with integer entries, by fraction-free
+/// (Bareiss) elimination. Panics if an entry is not an integer or the result
+/// overflows `i64`.
+pub fn determinant_exact(matrix: &Matrix) -> i64 {
+    let n = matrix.rows;
+    let mut a = integer_entries(matrix);
+
+    match bareiss(&mut a, n, n) {
+        Some((d, swaps)) => {
+            let det = if swaps % 2 == 0 { d } else { -d };
+            det.try_into().expect("Determinant overflows i64")
+        }
+        None => 0,
+    }
+}
+
+/// Exact inverse of a matrix with integer entries as row-major fractions, or
+/// `None` if it is singular. Bareiss elimination on `[A | I]` yields
+/// `[d I | adj]`, so no rounding is involved.
+pub fn inverse_exact(matrix: &Matrix) -> Option<Vec<Fraction>> {
+    let n = matrix.rows;
+    let entries = integer_entries(matrix);
+
+    let cols = 2 * n;
+    let mut augmented = vec![0; n * cols];
+    for i in 0..n {
+        augmented[i * cols..i * cols + n].copy_from_slice(&entries[i * n..(i + 1) * n]);
+        augmented[i * cols + n + i] = 1;
+    }
+
+    let (d, _) = bareiss(&mut augmented, n, cols)?;
+    let inverse = (0..n)
+        .flat_map(|i| (n..cols).map(move |j| (i, j)))
+        .map(|(i, j)| Fraction::reduce(augmented[i * cols + j], d))
+        .collect();
+    Some(inverse)
+}
+
+/// Reduces `a` in place to reduced row echelon form by Gauss-Jordan
+/// elimination with partial pivoting, returning the number of pivots (the
+/// rank). Entries no larger than `max(rows, cols) * EPSILON * max|a|` count
+/// as zero.
+pub fn gaussian_eliminate(a: &mut Matrix) -> usize {
+    let cols = a.cols;
+    eliminate(a, cols)
+}
+
+/// Reduced row echelon form of `matrix`.
+pub fn rref(matrix: &Matrix) -> Matrix {
+    let mut result = matrix.clone();
+    gaussian_eliminate(&mut result);
+    result
+}
+
+pub fn rank(matrix: &Matrix) -> usize {
+    gaussian_eliminate(&mut matrix.clone())
+}
+
+// pivots only in the first `pivot_cols` columns, so augmented matrices like
+// [A | I] carry the rest along
+fn eliminate(a: &mut Matrix, pivot_cols: usize) -> usize {
+    gauss_jordan(&mut a.data, a.rows, a.cols, pivot_cols)
+}
+
 /*
 VECTOR OPS
 */
@@ -291,3 +406,412 @@ pub fn angle(a: &Matrix, b: &Matrix) -> f64 {
 
     (dot_product / (mag_a * mag_b)).acos()
 }
+
+/// `a bᵀ` as an `a.len() x b.len()` matrix; row or column vectors alike.
+pub fn outer(a: &Matrix, b: &Matrix) -> Matrix {
+    assert!(
+        (a.rows == 1 || a.cols == 1) && (b.rows == 1 || b.cols == 1),
+        "Outer product requires 1xN or Nx1 vectors"
+    );
+
+    let data = a
+        .data
+        .iter()
+        .flat_map(|&x| b.data.iter().map(move |&y| x * y))
+        .collect();
+    Matrix::new(a.data.len(), b.data.len(), data)
+}
+
+/*
+FALLIBLE VECTOR OPS
+Same as above, but returning errors instead of panicking.
+*/
+
+fn check_vector(vec: &Matrix) -> Result<(), Error> {
+    if vec.rows == 1 || vec.cols == 1 {
+        Ok(())
+    } else {
+        Err(Error::NotAVector)
+    }
+}
+
+pub fn try_dot(a: &Matrix, b: &Matrix) -> Result<f64, Error> {
+    check_vector(a)?;
+    check_vector(b)?;
+    if a.data.len() != b.data.len() {
+        return Err(Error::MatrixSizeMismatch);
+    }
+
+    Ok(a.data.iter().zip(&b.data).map(|(x, y)| x * y).sum())
+}
+
+pub fn try_magnitude(vec: &Matrix) -> Result<f64, Error> {
+    check_vector(vec)?;
+
+    Ok(vec.data.iter().map(|x| x * x).sum::<f64>().sqrt())
+}
+
+pub fn try_normalize(vec: &Matrix) -> Result<Matrix, Error> {
+    let mag = try_magnitude(vec)?;
+    if mag == 0.0 {
+        return Err(Error::ZeroVector);
+    }
+
+    Ok(scalar_mul(vec, 1.0 / mag))
+}
+
+/// Cross product of two 3-element vectors (row or column); the result is a
+/// 3x1 column. Any other length is a `MatrixSizeMismatch`.
+pub fn try_cross(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+    check_vector(a)?;
+    check_vector(b)?;
+    if a.data.len() != 3 || b.data.len() != 3 {
+        return Err(Error::MatrixSizeMismatch);
+    }
+
+    let (x1, y1, z1) = (a.data[0], a.data[1], a.data[2]);
+    let (x2, y2, z2) = (b.data[0], b.data[1], b.data[2]);
+
+    Ok(Matrix::new(
+        3,
+        1,
+        vec![y1 * z2 - z1 * y2, z1 * x2 - x1 * z2, x1 * y2 - y1 * x2],
+    ))
+}
+
+pub fn try_projection(a: &Matrix, b: &Matrix) -> Result<Matrix, Error> {
+    let dot_product = try_dot(a, b)?;
+    let mag_b_sq = try_dot(b, b)?;
+    if mag_b_sq == 0.0 {
+        return Err(Error::ZeroVector);
+    }
+
+    Ok(scalar_mul(b, dot_product / mag_b_sq))
+}
+
+pub fn try_angle(a: &Matrix, b: &Matrix) -> Result<f64, Error> {
+    let dot_product = try_dot(a, b)?;
+    let mag_a = try_magnitude(a)?;
+    let mag_b = try_magnitude(b)?;
+    if mag_a == 0.0 || mag_b == 0.0 {
+        return Err(Error::ZeroVector);
+    }
+
+    Ok((dot_product / (mag_a * mag_b)).clamp(-1.0, 1.0).acos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(data: &[f64]) -> Matrix {
+        Matrix::new(data.len(), 1, data.to_vec())
+    }
+
+    #[test]
+    fn test_try_vector_ops_match_panicking_versions() {
+        let a = col(&[1.0, 2.0, 3.0]);
+        let b = col(&[-2.0, 0.5, 4.0]);
+
+        assert_eq!(try_dot(&a, &b).unwrap(), dot(&a, &b));
+        assert_eq!(try_magnitude(&a).unwrap(), magnitude(&a));
+        assert_eq!(try_normalize(&a).unwrap(), normalize(&a));
+        assert_eq!(try_cross(&a, &b).unwrap(), cross(&a, &b));
+        assert_eq!(try_projection(&a, &b).unwrap(), projection(&a, &b));
+        assert_eq!(try_angle(&a, &b).unwrap(), angle(&a, &b));
+
+        // row vectors work too
+        let row = Matrix::new(1, 3, vec![1.0, 2.0, 3.0]);
+        assert_eq!(try_cross(&row, &b).unwrap(), cross(&a, &b));
+    }
+
+    #[test]
+    fn test_try_vector_ops_not_a_vector() {
+        let m = Matrix::zeros(2, 2);
+        let v = col(&[1.0, 2.0]);
+
+        assert!(matches!(try_dot(&m, &v), Err(Error::NotAVector)));
+        assert!(matches!(try_dot(&v, &m), Err(Error::NotAVector)));
+        assert!(matches!(try_magnitude(&m), Err(Error::NotAVector)));
+        assert!(matches!(try_normalize(&m), Err(Error::NotAVector)));
+        assert!(matches!(try_cross(&m, &v), Err(Error::NotAVector)));
+        assert!(matches!(try_projection(&v, &m), Err(Error::NotAVector)));
+        assert!(matches!(try_angle(&m, &v), Err(Error::NotAVector)));
+    }
+
+    #[test]
+    fn test_try_vector_ops_size_mismatch() {
+        let a = col(&[1.0, 2.0]);
+        let b = col(&[1.0, 2.0, 3.0]);
+
+        assert!(matches!(try_dot(&a, &b), Err(Error::MatrixSizeMismatch)));
+        assert!(matches!(try_cross(&a, &a), Err(Error::MatrixSizeMismatch)));
+        assert!(matches!(
+            try_projection(&a, &b),
+            Err(Error::MatrixSizeMismatch)
+        ));
+        assert!(matches!(try_angle(&a, &b), Err(Error::MatrixSizeMismatch)));
+    }
+
+    #[test]
+    fn test_try_vector_ops_zero_vector() {
+        let zero = col(&[0.0, 0.0, 0.0]);
+        let v = col(&[1.0, 0.0, 0.0]);
+
+        assert!(matches!(try_normalize(&zero), Err(Error::ZeroVector)));
+        assert!(matches!(try_projection(&v, &zero), Err(Error::ZeroVector)));
+        assert!(matches!(try_angle(&zero, &v), Err(Error::ZeroVector)));
+        assert!(matches!(try_angle(&v, &zero), Err(Error::ZeroVector)));
+        assert_eq!(try_projection(&zero, &v).unwrap(), zero);
+    }
+
+    #[test]
+    fn test_det_sign_orientation() {
+        // triangle (0,0), (1,0), (0,1) as rows [x, y, 1]: counter-clockwise
+        let ccw = Matrix::new(3, 3, vec![0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0]);
+        assert_eq!(det_sign(&ccw), 1);
+
+        // same triangle with two vertices exchanged: clockwise
+        let cw = Matrix::new(3, 3, vec![1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0]);
+        assert_eq!(det_sign(&cw), -1);
+
+        // collinear points
+        let flat = Matrix::new(3, 3, vec![0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(det_sign(&flat), 0);
+
+        // needs a pivot swap: det = -2
+        let swapped = Matrix::new(2, 2, vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(det_sign(&swapped), -1);
+        assert_eq!(det_sign(&Matrix::identity(4)), 1);
+
+        for m in [&ccw, &cw, &swapped] {
+            assert_eq!(det_sign(m), determinant(m).signum() as i32);
+        }
+
+        // the singularity test is relative to the matrix's scale
+        assert_eq!(det_sign(&scalar_mul(&Matrix::identity(3), 1e-10)), 1);
+        assert_eq!(det_sign(&scalar_mul(&cw, 1e-12)), -1);
+        assert_eq!(det_sign(&scalar_mul(&flat, 1e-12)), 0);
+        assert_eq!(det_sign(&scalar_mul(&flat, 1e12)), 0);
+    }
+
+    #[test]
+    fn test_inv_known_inverses() {
+        let cases = [
+            (
+                Matrix::new(2, 2, vec![4.0, 7.0, 2.0, 6.0]),
+                vec![0.6, -0.7, -0.2, 0.4],
+            ),
+            (
+                // needs a row swap for the first pivot
+                Matrix::new(3, 3, vec![0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0, 8.0]),
+                vec![-4.5, 7.0, -1.5, -2.0, 4.0, -1.0, 1.5, -2.0, 0.5],
+            ),
+            (Matrix::identity(4), Matrix::identity(4).data),
+            (Matrix::new(1, 1, vec![-8.0]), vec![-0.125]),
+        ];
+
+        for (m, want) in cases {
+            let inverse = inv(&m).unwrap();
+            assert_eq!(inverse.data, want, "inverse of {m:?}");
+            let product = mul(&m, &inverse).unwrap();
+            for (got, want) in product.data.iter().zip(&Matrix::identity(m.rows).data) {
+                assert!((got - want).abs() < 1e-5);
+            }
+        }
+
+        assert_eq!(inv(&Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 4.0])), None);
+        assert_eq!(
+            inv(&Matrix::new(
+                3,
+                3,
+                vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rref_and_rank() {
+        let m = Matrix::new(
+            3,
+            4,
+            vec![
+                1.0, 2.0, -1.0, -4.0, 2.0, 3.0, -1.0, -11.0, -2.0, 0.0, -3.0, 22.0,
+            ],
+        );
+        let reduced = rref(&m);
+        let want = [1.0, 0.0, 0.0, -8.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, -2.0];
+        for (got, want) in reduced.data.iter().zip(want) {
+            assert!((got - want).abs() < 1e-12, "{reduced:?}");
+        }
+        assert_eq!(rank(&m), 3);
+
+        // rounding leaves ~1e-16 residue that must not count as a pivot
+        let singular = Matrix::new(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let mut reduced = singular.clone();
+        assert_eq!(gaussian_eliminate(&mut reduced), 2);
+        assert_eq!(&reduced.data[6..], &[0.0, 0.0, 0.0]);
+        assert!((reduced.get(0, 2) + 1.0).abs() < 1e-12);
+        assert!((reduced.get(1, 2) - 2.0).abs() < 1e-12);
+
+        assert_eq!(rank(&Matrix::zeros(3, 2)), 0);
+        assert_eq!(
+            rank(&Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0])),
+            1
+        );
+        assert_eq!(rank(&Matrix::identity(5)), 5);
+    }
+
+    #[test]
+    fn test_elementwise_ops_match_scalar_loops() {
+        // odd sizes exercise the scalar tail after the 4-lane chunks
+        for (rows, cols) in [(1, 1), (3, 3), (4, 5), (7, 9)] {
+            let n = rows * cols;
+            let a = Matrix::new(
+                rows,
+                cols,
+                (0..n).map(|i| (i as f64 * 1.7).sin() * 1e3).collect(),
+            );
+            let b = Matrix::new(rows, cols, (0..n).map(|i| 1.0 / (i as f64 + 0.3)).collect());
+
+            let scalar =
+                |f: &dyn Fn(usize) -> f64| -> Vec<u64> { (0..n).map(|i| f(i).to_bits()).collect() };
+            let bits = |m: Matrix| -> Vec<u64> { m.data.iter().map(|x| x.to_bits()).collect() };
+
+            assert_eq!(
+                bits(add(&a, &b).unwrap()),
+                scalar(&|i| a.data[i] + b.data[i])
+            );
+            assert_eq!(
+                bits(sub(&a, &b).unwrap()),
+                scalar(&|i| a.data[i] - b.data[i])
+            );
+            assert_eq!(bits(scalar_mul(&a, -0.1)), scalar(&|i| a.data[i] * -0.1));
+            assert_eq!(bits(scalar_add(&a, 0.1)), scalar(&|i| a.data[i] + 0.1));
+            assert_eq!(bits(power(&b, 1.5)), scalar(&|i| b.data[i].powf(1.5)));
+            assert_eq!(bits(map(&a, f64::abs)), scalar(&|i| a.data[i].abs()));
+        }
+    }
+
+    #[test]
+    fn test_determinant_exact_where_float_rounds() {
+        // (1e8 + 1)(1e8 - 1) = 1e16 - 1 is not representable in f64
+        let a = Matrix::new(2, 2, vec![1e8 + 1.0, 1e8, 1e8, 1e8 - 1.0]);
+        assert_ne!(determinant(&a), -1.0);
+        assert_eq!(determinant_exact(&a), -1);
+
+        let b = Matrix::new(3, 3, vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]);
+        assert_eq!(determinant_exact(&b), 4);
+        // a row swap is needed and flips the sign
+        let c = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(determinant_exact(&c), -1);
+        assert_eq!(
+            determinant_exact(&Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 4.0])),
+            0
+        );
+    }
+
+    #[test]
+    fn test_inverse_exact() {
+        use num_traits::{One, Zero};
+
+        let a = Matrix::new(3, 3, vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]);
+        let inverse = inverse_exact(&a).unwrap();
+        assert_eq!(inverse[0], Fraction::new(3, 4));
+        assert_eq!(inverse[4], Fraction::from(1));
+
+        // the float path rounds to 6 decimal places
+        let third = Matrix::new(2, 2, vec![3.0, 0.0, 0.0, 1.0]);
+        assert_eq!(inv(&third).unwrap().get(0, 0), 0.333333);
+        assert_eq!(inverse_exact(&third).unwrap()[0], Fraction::new(1, 3));
+        assert_eq!(
+            inverse_exact(&Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 4.0])),
+            None
+        );
+
+        // A * A^-1 is exactly I for random integer matrices
+        let mut rng = crate::math::random::Rng::new(4);
+        for _ in 0..200 {
+            let n = 1 + rng.gen_range(5) as usize;
+            let data = (0..n * n)
+                .map(|_| rng.gen_range(21) as f64 - 10.0)
+                .collect();
+            let m = Matrix::new(n, n, data);
+            assert_eq!(determinant_exact(&m) as f64, determinant(&m));
+            let Some(inverse) = inverse_exact(&m) else {
+                assert_eq!(determinant_exact(&m), 0);
+                continue;
+            };
+            for i in 0..n {
+                for j in 0..n {
+                    let dot = (0..n).fold(Fraction::zero(), |acc, k| {
+                        acc + Fraction::from(m.get(i, k) as i64) * inverse[k * n + j]
+                    });
+                    let want = if i == j {
+                        Fraction::one()
+                    } else {
+                        Fraction::zero()
+                    };
+                    assert_eq!(dot, want);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Matrix entries must be integers")]
+    fn test_exact_rejects_non_integers() {
+        determinant_exact(&Matrix::new(1, 1, vec![0.5]));
+    }
+
+    #[test]
+    fn test_outer_and_rank1_update() {
+        let u = col(&[1.0, 2.0, 3.0]);
+        let v = Matrix::new(1, 2, vec![4.0, -1.0]);
+
+        #[rustfmt::skip]
+        let expected = Matrix::new(3, 2, vec![
+            4.0, -1.0,
+            8.0, -2.0,
+            12.0, -3.0,
+        ]);
+        assert_eq!(outer(&u, &v), expected);
+        assert_eq!(outer(&v, &u), transpose(&expected));
+
+        let mut a = Matrix::new(3, 2, vec![1.0, 0.0, 0.0, 1.0, 2.0, 2.0]);
+        let reference = add(&a, &scalar_mul(&expected, -0.5)).unwrap();
+        a.rank1_update(-0.5, &u, &v);
+        assert_eq!(a, reference);
+    }
+
+    #[test]
+    #[should_panic(expected = "Outer product requires 1xN or Nx1 vectors")]
+    fn test_outer_rejects_matrices() {
+        outer(&Matrix::identity(2), &col(&[1.0]));
+    }
+
+    #[test]
+    fn test_trace_of_product_matches_full_product() {
+        use crate::math::random::Rng;
+
+        let trace = |m: &Matrix| (0..m.rows).map(|i| m.get(i, i)).sum::<f64>();
+        let mut rng = Rng::new(17);
+        for (n, k) in [(1, 1), (3, 5), (6, 2)] {
+            let a = Matrix::new(n, k, (0..n * k).map(|_| rng.next_f64() - 0.5).collect());
+            let b = Matrix::new(k, n, (0..k * n).map(|_| rng.next_f64() - 0.5).collect());
+            let want = trace(&mul(&a, &b).unwrap());
+            assert!((trace_of_product(&a, &b).unwrap() - want).abs() < 1e-12);
+        }
+
+        let a = Matrix::zeros(2, 3);
+        assert!(matches!(
+            trace_of_product(&a, &Matrix::zeros(2, 2)),
+            Err(Error::MatrixSizeMismatch)
+        ));
+        assert!(matches!(
+            trace_of_product(&a, &Matrix::zeros(3, 3)),
+            Err(Error::MatrixNotSquare)
+        ));
+    }
+}
@@ -0,0 +1,764 @@
+use crate::math::matrix::matrix::Matrix;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// A sparse matrix in compressed sparse row (CSR) form.
+///
+/// The column indices of row `i` are `col_idx[row_ptr[i]..row_ptr[i + 1]]`,
+/// sorted ascending, with the matching entries in `values`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub row_ptr: Vec<usize>,
+    pub col_idx: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Builds a matrix from `(row, col, value)` triplets in any order.
+    /// Duplicate positions are summed.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Self {
+        let mut sorted = triplets.to_vec();
+        for &(r, c, _) in &sorted {
+            assert!(
+                r < rows && c < cols,
+                "Triplet ({r}, {c}) out of bounds for a {rows}x{cols} matrix"
+            );
+        }
+        sorted.sort_by_key(|&(r, c, _)| (r, c));
+
+        let mut row_ptr = vec![0; rows + 1];
+        let mut col_idx: Vec<usize> = Vec::with_capacity(sorted.len());
+        let mut values: Vec<f64> = Vec::with_capacity(sorted.len());
+        let mut last = None;
+
+        for (r, c, v) in sorted {
+            if last == Some((r, c)) {
+                *values.last_mut().unwrap() += v;
+                continue;
+            }
+            col_idx.push(c);
+            values.push(v);
+            row_ptr[r + 1] += 1;
+            last = Some((r, c));
+        }
+        for i in 0..rows {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        Self {
+            rows,
+            cols,
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+
+    /// Compresses a dense matrix, dropping exact zeros.
+    pub fn from_dense(matrix: &Matrix) -> Self {
+        let mut triplets = Vec::new();
+        for i in 0..matrix.rows {
+            for j in 0..matrix.cols {
+                let v = matrix.get(i, j);
+                if v != 0.0 {
+                    triplets.push((i, j, v));
+                }
+            }
+        }
+        Self::from_triplets(matrix.rows, matrix.cols, &triplets)
+    }
+
+    pub fn to_dense(&self) -> Matrix {
+        let mut dense = Matrix::zeros(self.rows, self.cols);
+        for (i, j, v) in self.iter() {
+            dense.set(i, j, v);
+        }
+        dense
+    }
+
+    /// Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        let range = self.row_ptr[row]..self.row_ptr[row + 1];
+        match self.col_idx[range.clone()].binary_search(&col) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Iterates over the stored `(row, col, value)` entries in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        (0..self.rows).flat_map(move |i| {
+            (self.row_ptr[i]..self.row_ptr[i + 1])
+                .map(move |k| (i, self.col_idx[k], self.values[k]))
+        })
+    }
+
+    /// Transpose, built directly in CSR form by counting entries per column.
+    pub fn transpose(&self) -> SparseMatrix {
+        let mut row_ptr = vec![0; self.cols + 1];
+        for &c in &self.col_idx {
+            row_ptr[c + 1] += 1;
+        }
+        for i in 0..self.cols {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        // walking rows in order keeps each output row's columns sorted
+        let mut next = row_ptr.clone();
+        let mut col_idx = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for (i, j, v) in self.iter() {
+            col_idx[next[j]] = i;
+            values[next[j]] = v;
+            next[j] += 1;
+        }
+
+        SparseMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+
+    /// Sparse matrix-vector product `A x`.
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            x.len(),
+            self.cols,
+            "Vector length must match matrix columns"
+        );
+
+        (0..self.rows)
+            .map(|i| {
+                (self.row_ptr[i]..self.row_ptr[i + 1])
+                    .map(|k| self.values[k] * x[self.col_idx[k]])
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Sparse-sparse product `A B` (Gustavson's algorithm).
+///
+/// Each output row is accumulated in a dense sparse accumulator (SPA) of
+/// width `b.cols`, so the cost is proportional to the flops plus `b.cols`.
+pub fn spgemm(a: &SparseMatrix, b: &SparseMatrix) -> SparseMatrix {
+    assert_eq!(a.cols, b.rows, "Matrix size mismatch");
+
+    let mut row_ptr = vec![0; a.rows + 1];
+    let mut col_idx = Vec::new();
+    let mut values = Vec::new();
+
+    let mut acc = vec![0.0; b.cols];
+    let mut occupied = vec![false; b.cols];
+    let mut touched = Vec::new();
+
+    for i in 0..a.rows {
+        for ka in a.row_ptr[i]..a.row_ptr[i + 1] {
+            let (k, av) = (a.col_idx[ka], a.values[ka]);
+            for kb in b.row_ptr[k]..b.row_ptr[k + 1] {
+                let j = b.col_idx[kb];
+                if !occupied[j] {
+                    occupied[j] = true;
+                    touched.push(j);
+                }
+                acc[j] += av * b.values[kb];
+            }
+        }
+
+        touched.sort_unstable();
+        for &j in &touched {
+            col_idx.push(j);
+            values.push(acc[j]);
+            acc[j] = 0.0;
+            occupied[j] = false;
+        }
+        touched.clear();
+        row_ptr[i + 1] = col_idx.len();
+    }
+
+    SparseMatrix {
+        rows: a.rows,
+        cols: b.cols,
+        row_ptr,
+        col_idx,
+        values,
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Solves `A x = b` for symmetric positive definite `A` by conjugate gradient.
+///
+/// Stops once `||b - A x|| <= tol * ||b||`; returns `None` if that doesn't
+/// happen within `max_iter` iterations.
+pub fn conjugate_gradient(
+    a: &SparseMatrix,
+    b: &[f64],
+    tol: f64,
+    max_iter: usize,
+) -> Option<Vec<f64>> {
+    assert_eq!(a.rows, a.cols, "Matrix must be square");
+    assert_eq!(
+        b.len(),
+        a.rows,
+        "Right-hand side length must match matrix rows"
+    );
+
+    let mut x = vec![0.0; b.len()];
+    let mut r = b.to_vec();
+    let mut p = r.clone();
+    let mut rr = dot(&r, &r);
+    let target = tol * norm(b);
+
+    if rr.sqrt() <= target {
+        return Some(x);
+    }
+
+    for _ in 0..max_iter {
+        let ap = a.mul_vec(&p);
+        let alpha = rr / dot(&p, &ap);
+
+        for i in 0..x.len() {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+
+        let rr_next = dot(&r, &r);
+        if rr_next.sqrt() <= target {
+            return Some(x);
+        }
+
+        let beta = rr_next / rr;
+        for (pi, ri) in p.iter_mut().zip(&r) {
+            *pi = ri + beta * *pi;
+        }
+        rr = rr_next;
+    }
+
+    None
+}
+
+/// Solves `A x = b` for general square `A` by restarted GMRES.
+///
+/// Each cycle builds a Krylov basis of up to `restart` vectors with Arnoldi
+/// iteration and minimizes the residual over it using Givens rotations.
+/// Stops once `||b - A x|| <= tol * ||b||`; returns `None` if that doesn't
+/// happen within `max_iter` total inner iterations.
+pub fn gmres(
+    a: &SparseMatrix,
+    b: &[f64],
+    restart: usize,
+    tol: f64,
+    max_iter: usize,
+) -> Option<Vec<f64>> {
+    assert_eq!(a.rows, a.cols, "Matrix must be square");
+    assert_eq!(
+        b.len(),
+        a.rows,
+        "Right-hand side length must match matrix rows"
+    );
+    assert!(restart > 0, "Restart length must be positive");
+
+    let n = b.len();
+    let m = restart.min(n.max(1));
+    let target = tol * norm(b);
+    let mut x = vec![0.0; n];
+    let mut iters = 0;
+
+    loop {
+        let r: Vec<f64> = a
+            .mul_vec(&x)
+            .iter()
+            .zip(b)
+            .map(|(ax, bi)| bi - ax)
+            .collect();
+        let beta = norm(&r);
+        if beta <= target {
+            return Some(x);
+        }
+        if iters >= max_iter {
+            return None;
+        }
+
+        let mut basis = vec![r.iter().map(|ri| ri / beta).collect::<Vec<f64>>()];
+        // h[j] is column j of the (m + 1) x m Hessenberg matrix
+        let mut h: Vec<Vec<f64>> = Vec::with_capacity(m);
+        let mut cs: Vec<f64> = Vec::with_capacity(m);
+        let mut sn: Vec<f64> = Vec::with_capacity(m);
+        let mut g = vec![0.0; m + 1];
+        g[0] = beta;
+
+        for j in 0..m {
+            iters += 1;
+
+            // Arnoldi step with modified Gram-Schmidt
+            let mut w = a.mul_vec(&basis[j]);
+            let mut col = vec![0.0; j + 2];
+            for (i, v) in basis.iter().enumerate() {
+                col[i] = dot(&w, v);
+                for (wk, vk) in w.iter_mut().zip(v) {
+                    *wk -= col[i] * vk;
+                }
+            }
+            let h_next = norm(&w);
+            col[j + 1] = h_next;
+
+            for i in 0..j {
+                let t = cs[i] * col[i] + sn[i] * col[i + 1];
+                col[i + 1] = -sn[i] * col[i] + cs[i] * col[i + 1];
+                col[i] = t;
+            }
+            let d = col[j].hypot(col[j + 1]);
+            cs.push(col[j] / d);
+            sn.push(col[j + 1] / d);
+            col[j] = d;
+            col[j + 1] = 0.0;
+            h.push(col);
+
+            g[j + 1] = -sn[j] * g[j];
+            g[j] *= cs[j];
+
+            if g[j + 1].abs() <= target || h_next == 0.0 || iters >= max_iter {
+                break;
+            }
+            basis.push(w.iter().map(|wk| wk / h_next).collect());
+        }
+
+        // back-substitute the k x k triangle and update x
+        let k = h.len();
+        let mut y = vec![0.0; k];
+        for i in (0..k).rev() {
+            let s: f64 = ((i + 1)..k).map(|c| h[c][i] * y[c]).sum();
+            y[i] = (g[i] - s) / h[i][i];
+        }
+        for (yi, v) in y.iter().zip(&basis) {
+            for (xk, vk) in x.iter_mut().zip(v) {
+                *xk += yi * vk;
+            }
+        }
+    }
+}
+
+/// Failure to read a Matrix Market file.
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    /// Missing or malformed `%%MatrixMarket` banner or size line.
+    InvalidHeader,
+    /// A valid banner describing a format this reader does not handle.
+    Unsupported(String),
+    /// Malformed or out-of-bounds entry on the given 1-based line.
+    InvalidEntry(usize),
+    EntryCount {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(err) => write!(f, "I/O error: {err}"),
+            ParseError::InvalidHeader => write!(f, "Invalid Matrix Market header"),
+            ParseError::Unsupported(what) => write!(f, "Unsupported Matrix Market format: {what}"),
+            ParseError::InvalidEntry(line) => write!(f, "Invalid entry on line {line}"),
+            ParseError::EntryCount { expected, found } => {
+                write!(f, "Expected {expected} entries, found {found}")
+            }
+        }
+    }
+}
+
+impl StdError for ParseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ParseError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+impl SparseMatrix {
+    /// Reads a Matrix Market `coordinate` file with `real`, `integer` or
+    /// `pattern` entries (pattern entries read as 1.0) and `general`,
+    /// `symmetric` or `skew-symmetric` storage. Indices in the file are
+    /// 1-based.
+    pub fn read_matrix_market(r: &mut impl Read) -> Result<SparseMatrix, ParseError> {
+        let mut lines = BufReader::new(r).lines().enumerate();
+
+        let banner = match lines.next() {
+            Some((_, line)) => line?.to_lowercase(),
+            None => return Err(ParseError::InvalidHeader),
+        };
+        let fields: Vec<&str> = banner.split_whitespace().collect();
+        let [head, object, format, field, symmetry] = fields[..] else {
+            return Err(ParseError::InvalidHeader);
+        };
+        if head != "%%matrixmarket" {
+            return Err(ParseError::InvalidHeader);
+        }
+        if object != "matrix" || format != "coordinate" {
+            return Err(ParseError::Unsupported(format!("{object} {format}")));
+        }
+        let pattern = match field {
+            "real" | "integer" => false,
+            "pattern" => true,
+            other => return Err(ParseError::Unsupported(other.to_string())),
+        };
+        let mirror = match symmetry {
+            "general" => None,
+            "symmetric" => Some(1.0),
+            "skew-symmetric" => Some(-1.0),
+            other => return Err(ParseError::Unsupported(other.to_string())),
+        };
+
+        // everything after the banner, minus comments and blank lines
+        let mut content = lines.filter_map(|(i, line)| match line {
+            Ok(line) if line.trim().is_empty() || line.starts_with('%') => None,
+            Ok(line) => Some(Ok((i + 1, line))),
+            Err(err) => Some(Err(err)),
+        });
+
+        let size = match content.next() {
+            Some(line) => line?.1,
+            None => return Err(ParseError::InvalidHeader),
+        };
+        let size: Vec<usize> = size
+            .split_whitespace()
+            .map(|s| s.parse().map_err(|_| ParseError::InvalidHeader))
+            .collect::<Result<_, _>>()?;
+        let [rows, cols, expected] = size[..] else {
+            return Err(ParseError::InvalidHeader);
+        };
+        // `from_triplets` allocates `rows + 1` offsets before any entry is
+        // read, so the header alone must not ask for more than 32-bit
+        // indices can address
+        const MAX_DIMENSION: usize = u32::MAX as usize;
+        if rows.max(cols) > MAX_DIMENSION || rows.checked_add(1).is_none() {
+            return Err(ParseError::InvalidHeader);
+        }
+
+        // the count is only a claim until the entries are read
+        let mut triplets = Vec::with_capacity(expected.min(1 << 16));
+        let mut found = 0;
+        for line in content {
+            let (number, line) = line?;
+            let entry = parse_entry(&line, pattern)
+                .filter(|&(r, c, _)| (1..=rows).contains(&r) && (1..=cols).contains(&c))
+                .ok_or(ParseError::InvalidEntry(number))?;
+
+            let (r, c, v) = (entry.0 - 1, entry.1 - 1, entry.2);
+            triplets.push((r, c, v));
+            if let Some(sign) = mirror.filter(|_| r != c) {
+                triplets.push((c, r, sign * v));
+            }
+            found += 1;
+        }
+        if found != expected {
+            return Err(ParseError::EntryCount { expected, found });
+        }
+
+        Ok(Self::from_triplets(rows, cols, &triplets))
+    }
+}
+
+fn parse_entry(line: &str, pattern: bool) -> Option<(usize, usize, f64)> {
+    let mut parts = line.split_whitespace();
+    let r = parts.next()?.parse().ok()?;
+    let c = parts.next()?.parse().ok()?;
+    let v = if pattern {
+        1.0
+    } else {
+        parts.next()?.parse().ok()?
+    };
+    parts.next().is_none().then_some((r, c, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::ops::{inv, mul};
+
+    // 1D Poisson matrix tridiag(-1, 2, -1)
+    fn laplacian(n: usize) -> SparseMatrix {
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.0));
+            if i > 0 {
+                triplets.push((i, i - 1, -1.0));
+                triplets.push((i - 1, i, -1.0));
+            }
+        }
+        SparseMatrix::from_triplets(n, n, &triplets)
+    }
+
+    #[test]
+    fn test_from_triplets() {
+        let m = SparseMatrix::from_triplets(
+            3,
+            4,
+            &[
+                (2, 1, 5.0),
+                (0, 3, 1.0),
+                (0, 0, 2.0),
+                (2, 1, 1.0),
+                (1, 2, -3.0),
+            ],
+        );
+
+        assert_eq!(m.row_ptr, vec![0, 2, 3, 4]);
+        assert_eq!(m.col_idx, vec![0, 3, 2, 1]);
+        assert_eq!(m.values, vec![2.0, 1.0, -3.0, 6.0]);
+        assert_eq!(m.get(2, 1), 6.0);
+        assert_eq!(m.get(1, 1), 0.0);
+        assert_eq!(SparseMatrix::from_dense(&m.to_dense()), m);
+        assert_eq!(m.mul_vec(&[1.0, 2.0, 3.0, 4.0]), vec![6.0, -9.0, 12.0]);
+    }
+
+    #[test]
+    fn test_conjugate_gradient_matches_dense_solve() {
+        let n = 20;
+        let a = laplacian(n);
+        let b: Vec<f64> = (0..n).map(|i| (i as f64 * 0.7).sin()).collect();
+
+        let x = conjugate_gradient(&a, &b, 1e-12, 100).unwrap();
+
+        let residual: Vec<f64> = a
+            .mul_vec(&x)
+            .iter()
+            .zip(&b)
+            .map(|(ax, bi)| ax - bi)
+            .collect();
+        assert!(norm(&residual) <= 1e-11 * norm(&b));
+
+        let dense = mul(&inv(&a.to_dense()).unwrap(), &Matrix::new(n, 1, b.clone())).unwrap();
+        for (xi, di) in x.iter().zip(&dense.data) {
+            assert!((xi - di).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_conjugate_gradient_gives_up() {
+        let a = laplacian(50);
+        let b = vec![1.0; 50];
+
+        assert!(conjugate_gradient(&a, &b, 1e-12, 3).is_none());
+        assert_eq!(
+            conjugate_gradient(&a, &[0.0; 50], 1e-12, 3),
+            Some(vec![0.0; 50])
+        );
+    }
+
+    // tridiag(-1.3, 2.5, -0.7) plus a far off-diagonal coupling
+    fn convection(n: usize) -> SparseMatrix {
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.5));
+            if i > 0 {
+                triplets.push((i, i - 1, -1.3));
+                triplets.push((i - 1, i, -0.7));
+            }
+            if i >= 5 {
+                triplets.push((i, i - 5, 0.2));
+            }
+        }
+        SparseMatrix::from_triplets(n, n, &triplets)
+    }
+
+    #[test]
+    fn test_gmres_matches_dense_solve() {
+        let n = 30;
+        let a = convection(n);
+        let b: Vec<f64> = (0..n).map(|i| 1.0 + (i as f64).cos()).collect();
+        let dense = mul(&inv(&a.to_dense()).unwrap(), &Matrix::new(n, 1, b.clone())).unwrap();
+
+        for restart in [n, 10, 4] {
+            let x = gmres(&a, &b, restart, 1e-12, 1000).unwrap();
+
+            let residual: Vec<f64> = a
+                .mul_vec(&x)
+                .iter()
+                .zip(&b)
+                .map(|(ax, bi)| ax - bi)
+                .collect();
+            assert!(norm(&residual) <= 1e-11 * norm(&b), "restart {restart}");
+            for (xi, di) in x.iter().zip(&dense.data) {
+                assert!((xi - di).abs() < 1e-4, "restart {restart}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_gmres_gives_up() {
+        let a = convection(40);
+        let b = vec![1.0; 40];
+
+        assert!(gmres(&a, &b, 5, 1e-12, 3).is_none());
+        assert_eq!(gmres(&a, &[0.0; 40], 5, 1e-12, 3), Some(vec![0.0; 40]));
+    }
+
+    fn pseudo_random(rows: usize, cols: usize, seed: u64) -> SparseMatrix {
+        let mut state = seed;
+        let mut triplets = Vec::new();
+        for i in 0..rows {
+            for j in 0..cols {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                if (state >> 60) < 3 {
+                    triplets.push((i, j, ((state >> 33) % 19) as f64 - 9.0));
+                }
+            }
+        }
+        SparseMatrix::from_triplets(rows, cols, &triplets)
+    }
+
+    #[test]
+    fn test_transpose_matches_dense() {
+        use crate::math::matrix::ops::transpose;
+
+        let a = pseudo_random(7, 11, 1);
+        let t = a.transpose();
+
+        assert_eq!(t.to_dense(), transpose(&a.to_dense()));
+        assert_eq!(t.nnz(), a.nnz());
+        for i in 0..t.rows {
+            let row = &t.col_idx[t.row_ptr[i]..t.row_ptr[i + 1]];
+            assert!(row.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(t.transpose(), a);
+    }
+
+    #[test]
+    fn test_spgemm_matches_dense() {
+        let a = pseudo_random(9, 6, 2);
+        let b = pseudo_random(6, 13, 3);
+        let c = spgemm(&a, &b);
+
+        assert_eq!(c.to_dense(), mul(&a.to_dense(), &b.to_dense()).unwrap());
+        assert_eq!((c.rows, c.cols), (9, 13));
+        for i in 0..c.rows {
+            let row = &c.col_idx[c.row_ptr[i]..c.row_ptr[i + 1]];
+            assert!(row.windows(2).all(|w| w[0] < w[1]));
+        }
+
+        let lap = laplacian(10);
+        let sq = spgemm(&lap, &lap);
+        assert_eq!(
+            sq.to_dense(),
+            mul(&lap.to_dense(), &lap.to_dense()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_matrix_market() {
+        let mtx = "%%MatrixMarket matrix coordinate real general
+% a 3x4 sample with a comment and a blank line
+
+3 4 5
+1 1 2.5
+3 4 -1
+2 3 4e2
+1 4 7
+3 1 0.5
+";
+        let a = SparseMatrix::read_matrix_market(&mut mtx.as_bytes()).unwrap();
+
+        assert_eq!((a.rows, a.cols, a.nnz()), (3, 4, 5));
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![
+                (0, 0, 2.5),
+                (0, 3, 7.0),
+                (1, 2, 400.0),
+                (2, 0, 0.5),
+                (2, 3, -1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_matrix_market_symmetric_pattern() {
+        let mtx = "%%MatrixMarket matrix coordinate pattern symmetric\n3 3 3\n1 1\n2 1\n3 2\n";
+        let a = SparseMatrix::read_matrix_market(&mut mtx.as_bytes()).unwrap();
+
+        assert_eq!(a, a.transpose());
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![
+                (0, 0, 1.0),
+                (0, 1, 1.0),
+                (1, 0, 1.0),
+                (1, 2, 1.0),
+                (2, 1, 1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_matrix_market_errors() {
+        let parse = |s: &str| SparseMatrix::read_matrix_market(&mut s.as_bytes());
+
+        assert!(matches!(parse(""), Err(ParseError::InvalidHeader)));
+        assert!(matches!(
+            parse("%%MatrixMarket matrix array real general\n2 2\n"),
+            Err(ParseError::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n"),
+            Err(ParseError::Unsupported(_))
+        ));
+        // index 0 is out of range in a 1-based file
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n2 2 1\n0 1 1.0\n"),
+            Err(ParseError::InvalidEntry(3))
+        ));
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n"),
+            Err(ParseError::EntryCount {
+                expected: 2,
+                found: 1
+            })
+        ));
+        // an absurd entry count is reported, not allocated
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n2 2 18446744073709551615\n"),
+            Err(ParseError::EntryCount { found: 0, .. })
+        ));
+        // nor is a dimension that would overflow or exhaust memory
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n18446744073709551615 1 0\n"),
+            Err(ParseError::InvalidHeader)
+        ));
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n1099511627776 1 0\n"),
+            Err(ParseError::InvalidHeader)
+        ));
+        assert!(matches!(
+            parse("%%MatrixMarket matrix coordinate real general\n1 1099511627776 0\n"),
+            Err(ParseError::InvalidHeader)
+        ));
+    }
+}
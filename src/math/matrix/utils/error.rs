@@ -6,6 +6,8 @@ pub enum Error {
     MatrixSizeMismatch,
     MatrixNotSquare,
     SingularMatrix,
+    NotAVector,
+    ZeroVector,
 }
 
 impl fmt::Display for Error {
@@ -17,6 +19,8 @@ impl fmt::Display for Error {
             ),
             Error::MatrixNotSquare => write!(f, "Matrix is not square"),
             Error::SingularMatrix => write!(f, "Matrix is singular"),
+            Error::NotAVector => write!(f, "Expected a 1xN or Nx1 vector"),
+            Error::ZeroVector => write!(f, "Vector has zero magnitude"),
         }
     }
 }
@@ -1,18 +1,26 @@
-// use crate::matrix::matrix::Matrix;
-// use crate::matrix::*;
+// Element lists accept any numeric literal or expression (coerced with
+// `as f64`) and an optional trailing comma.
 
 // converts a input : sparse matrix to a dense matrix
 #[macro_export]
 macro_rules! Sparse {
-    ($rows:expr, $cols:expr, [$($row:expr, $col:expr, $val:expr),*]) => {
+    ($rows:expr, $cols:expr, [$($row:expr, $col:expr, $val:expr),* $(,)?]) => {
         {
-            let mut data = vec![0.0; $rows * $cols];
+            let rows: usize = $rows;
+            let cols: usize = $cols;
+            let mut data = vec![0.0; rows * cols];
             $(
-                data[$row * $cols + $col] = $val;
+                let (row, col): (usize, usize) = ($row, $col);
+                assert!(
+                    row < rows && col < cols,
+                    "Sparse! entry ({}, {}) out of bounds for a {}x{} matrix",
+                    row, col, rows, cols
+                );
+                data[row * cols + col] = $val as f64;
             )*
-            Matrix {
-                rows: $rows,
-                cols: $cols,
+            $crate::math::matrix::matrix::Matrix {
+                rows,
+                cols,
                 data,
             }
         }
@@ -21,18 +29,93 @@ macro_rules! Sparse {
 
 #[macro_export]
 macro_rules! Dense {
-    ($rows:expr, $cols:expr, [$($row:expr),*]) => {
+    ($rows:expr, $cols:expr, [$($row:expr),* $(,)?]) => {
         {
-            Matrix::new($rows, $cols, vec![$($row),*])
+            $crate::math::matrix::matrix::Matrix::new($rows, $cols, vec![$($row as f64),*])
         }
     };
 }
 
 #[macro_export]
 macro_rules! Vector {
-    ($rows:expr, [$($row:expr),*]) => {
+    ($rows:expr, [$($row:expr),* $(,)?]) => {
         {
-            Matrix::new($rows, 1, vec![$($row),*])
+            $crate::math::matrix::matrix::Matrix::new($rows, 1, vec![$($row as f64),*])
         }
     };
 }
+
+#[macro_export]
+macro_rules! Identity {
+    ($n:expr) => {
+        $crate::math::matrix::matrix::Matrix::identity($n)
+    };
+}
+
+#[macro_export]
+macro_rules! Zeros {
+    ($rows:expr, $cols:expr) => {
+        $crate::math::matrix::matrix::Matrix::zeros($rows, $cols)
+    };
+}
+
+// square matrix with the listed values on its main diagonal
+#[macro_export]
+macro_rules! Diag {
+    ([$($val:expr),* $(,)?]) => {
+        {
+            let diag: Vec<f64> = vec![$($val as f64),*];
+            let n = diag.len();
+            let mut m = $crate::math::matrix::matrix::Matrix::zeros(n, n);
+            for (i, v) in diag.into_iter().enumerate() {
+                m.set(i, i, v);
+            }
+            m
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_integer_literals_and_trailing_commas() {
+        let a = Dense!(2, 2, [1, 2, 3, 4]);
+        let b = Dense!(2, 2, [1.0, 2.0, 3.0, 4.0,]);
+        let c = Dense!(2, 2, [1, 2.0, 3, -4,]);
+
+        assert_eq!(a.data, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(a, b);
+        assert_eq!(c.data, vec![1.0, 2.0, 3.0, -4.0]);
+
+        let v = Vector!(3, [1, 2, 3,]);
+        assert_eq!((v.rows, v.cols), (3, 1));
+        assert_eq!(v.data, vec![1.0, 2.0, 3.0]);
+
+        let s = Sparse!(2, 3, [0, 1, 5, 1, 2, 7.5,]);
+        assert_eq!(s.data, vec![0.0, 5.0, 0.0, 0.0, 0.0, 7.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sparse! entry (2, 0) out of bounds for a 2x3 matrix")]
+    fn test_sparse_out_of_bounds() {
+        Sparse!(2, 3, [0, 0, 1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_identity_zeros_diag() {
+        let i = Identity!(3);
+        assert_eq!((i.rows, i.cols), (3, 3));
+        assert_eq!(i.data, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+        let z = Zeros!(2, 3);
+        assert_eq!((z.rows, z.cols), (2, 3));
+        assert_eq!(z.data, vec![0.0; 6]);
+
+        let d = Diag!([2, -1.5, 4,]);
+        assert_eq!((d.rows, d.cols), (3, 3));
+        assert_eq!(d.data, vec![2.0, 0.0, 0.0, 0.0, -1.5, 0.0, 0.0, 0.0, 4.0]);
+
+        assert_eq!(Diag!([1, 1, 1]), Identity!(3));
+        assert_eq!(Diag!([]), Zeros!(0, 0));
+    }
+}
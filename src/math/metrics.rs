@@ -0,0 +1,221 @@
+//! Classification metrics.
+//!
+//! Per-class metrics are derived from a confusion matrix whose rows are the
+//! true classes and columns the predicted ones. A class whose ratio has a zero
+//! denominator (never predicted, or never present) scores 0.
+
+use crate::math::matrix::matrix::Matrix;
+
+pub fn confusion_matrix(y_true: &[usize], y_pred: &[usize], num_classes: usize) -> Matrix {
+    assert_eq!(
+        y_true.len(),
+        y_pred.len(),
+        "Label and prediction counts must match"
+    );
+
+    let mut cm = Matrix::zeros(num_classes, num_classes);
+    for (&t, &p) in y_true.iter().zip(y_pred) {
+        assert!(
+            t < num_classes && p < num_classes,
+            "Class out of range for {num_classes} classes"
+        );
+        cm.set(t, p, cm.get(t, p) + 1.0);
+    }
+    cm
+}
+
+/// Fraction of samples on the diagonal.
+pub fn accuracy(cm: &Matrix) -> f64 {
+    let total: f64 = cm.data.iter().sum();
+    let correct: f64 = (0..cm.rows).map(|i| cm.get(i, i)).sum();
+    ratio(correct, total)
+}
+
+/// Per-class `TP / (TP + FP)`.
+pub fn precision(cm: &Matrix) -> Vec<f64> {
+    (0..cm.cols)
+        .map(|c| {
+            let predicted: f64 = (0..cm.rows).map(|r| cm.get(r, c)).sum();
+            ratio(cm.get(c, c), predicted)
+        })
+        .collect()
+}
+
+/// Per-class `TP / (TP + FN)`.
+pub fn recall(cm: &Matrix) -> Vec<f64> {
+    (0..cm.rows)
+        .map(|r| {
+            let actual: f64 = (0..cm.cols).map(|c| cm.get(r, c)).sum();
+            ratio(cm.get(r, r), actual)
+        })
+        .collect()
+}
+
+/// Per-class harmonic mean of precision and recall.
+pub fn f1(cm: &Matrix) -> Vec<f64> {
+    precision(cm)
+        .into_iter()
+        .zip(recall(cm))
+        .map(|(p, r)| ratio(2.0 * p * r, p + r))
+        .collect()
+}
+
+pub fn macro_precision(cm: &Matrix) -> f64 {
+    mean(&precision(cm))
+}
+
+pub fn macro_recall(cm: &Matrix) -> f64 {
+    mean(&recall(cm))
+}
+
+pub fn macro_f1(cm: &Matrix) -> f64 {
+    mean(&f1(cm))
+}
+
+/// ROC curve of a binary classifier as `(false positive rates, true positive
+/// rates)`, sweeping the threshold from above the highest score down to the
+/// lowest. Tied scores move together, so the curve starts at (0, 0) and ends
+/// at (1, 1).
+pub fn roc_curve(scores: &[f64], labels: &[bool]) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(
+        scores.len(),
+        labels.len(),
+        "Score and label counts must match"
+    );
+
+    let positives = labels.iter().filter(|&&l| l).count() as f64;
+    let negatives = labels.len() as f64 - positives;
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+    let mut fpr = vec![0.0];
+    let mut tpr = vec![0.0];
+    let (mut tp, mut fp) = (0.0, 0.0);
+
+    for (k, &i) in order.iter().enumerate() {
+        if labels[i] {
+            tp += 1.0;
+        } else {
+            fp += 1.0;
+        }
+        let last_of_tie = order
+            .get(k + 1)
+            .is_none_or(|&next| scores[next] != scores[i]);
+        if last_of_tie {
+            fpr.push(ratio(fp, negatives));
+            tpr.push(ratio(tp, positives));
+        }
+    }
+
+    (fpr, tpr)
+}
+
+/// Area under a curve given by its x and y coordinates, by the trapezoidal
+/// rule. `x` must be monotonic, as the output of `roc_curve` is.
+pub fn auc(x: &[f64], y: &[f64]) -> f64 {
+    assert_eq!(x.len(), y.len(), "Coordinate counts must match");
+
+    x.windows(2)
+        .zip(y.windows(2))
+        .map(|(xs, ys)| (xs[1] - xs[0]) * (ys[0] + ys[1]) / 2.0)
+        .sum()
+}
+
+fn ratio(num: f64, den: f64) -> f64 {
+    if den == 0.0 {
+        0.0
+    } else {
+        num / den
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    ratio(values.iter().sum(), values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(got: &[f64], want: &[f64]) {
+        assert_eq!(got.len(), want.len());
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() < 1e-12, "got {got:?}, want {want:?}");
+        }
+    }
+
+    #[test]
+    fn test_known_predictions() {
+        let y_true = [0, 0, 0, 0, 1, 1, 1, 2, 2, 2];
+        let y_pred = [0, 0, 1, 2, 1, 1, 0, 2, 2, 1];
+        let cm = confusion_matrix(&y_true, &y_pred, 3);
+
+        #[rustfmt::skip]
+        assert_eq!(cm.data, vec![
+            2.0, 1.0, 1.0,
+            1.0, 2.0, 0.0,
+            0.0, 1.0, 2.0,
+        ]);
+
+        assert_eq!(accuracy(&cm), 0.6);
+        // columns sum to 3, 4, 3
+        assert_close(&precision(&cm), &[2.0 / 3.0, 0.5, 2.0 / 3.0]);
+        // rows sum to 4, 3, 3
+        assert_close(&recall(&cm), &[0.5, 2.0 / 3.0, 2.0 / 3.0]);
+        assert_close(&f1(&cm), &[4.0 / 7.0, 4.0 / 7.0, 2.0 / 3.0]);
+
+        assert!((macro_precision(&cm) - 11.0 / 18.0).abs() < 1e-12);
+        assert!((macro_recall(&cm) - 11.0 / 18.0).abs() < 1e-12);
+        assert!((macro_f1(&cm) - (8.0 / 7.0 + 2.0 / 3.0) / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_missing_class_scores_zero() {
+        // class 2 is never present nor predicted
+        let cm = confusion_matrix(&[0, 1, 1], &[0, 1, 0], 3);
+
+        assert_close(&precision(&cm), &[0.5, 1.0, 0.0]);
+        assert_close(&recall(&cm), &[1.0, 0.5, 0.0]);
+        assert_eq!(f1(&cm)[2], 0.0);
+        assert_eq!(accuracy(&Matrix::zeros(2, 2)), 0.0);
+    }
+
+    #[test]
+    fn test_roc_perfect_classifier() {
+        let scores = [0.9, 0.8, 0.75, 0.3, 0.2, 0.1];
+        let labels = [true, true, true, false, false, false];
+        let (fpr, tpr) = roc_curve(&scores, &labels);
+
+        assert_eq!(fpr.first(), Some(&0.0));
+        assert_eq!((fpr.last(), tpr.last()), (Some(&1.0), Some(&1.0)));
+        assert_eq!(auc(&fpr, &tpr), 1.0);
+
+        // inverted scores are perfectly wrong
+        let flipped: Vec<f64> = scores.iter().map(|s| -s).collect();
+        let (fpr, tpr) = roc_curve(&flipped, &labels);
+        assert_eq!(auc(&fpr, &tpr), 0.0);
+    }
+
+    #[test]
+    fn test_roc_random_classifier() {
+        use crate::math::random::Rng;
+
+        let mut rng = Rng::new(11);
+        let scores: Vec<f64> = (0..20_000).map(|_| rng.next_f64()).collect();
+        let labels: Vec<bool> = (0..20_000).map(|_| rng.gen_range(2) == 1).collect();
+        let (fpr, tpr) = roc_curve(&scores, &labels);
+
+        assert!((auc(&fpr, &tpr) - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_roc_ties() {
+        // every score tied: a single diagonal step
+        let (fpr, tpr) = roc_curve(&[0.5; 4], &[true, false, true, false]);
+
+        assert_eq!(fpr, vec![0.0, 1.0]);
+        assert_eq!(tpr, vec![0.0, 1.0]);
+        assert_eq!(auc(&fpr, &tpr), 0.5);
+    }
+}
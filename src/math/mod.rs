@@ -1,2 +1,22 @@
+pub mod approx;
+pub mod complex;
+pub mod curves;
+pub mod diff;
+pub mod fft;
 pub mod fib;
+pub mod fraction;
 pub mod general;
+pub mod geometry;
+pub mod matrix;
+pub mod metrics;
+pub mod models;
+pub mod noise;
+pub mod ode;
+pub mod optimize;
+pub mod polynomial;
+pub mod preprocessing;
+pub mod quaternion;
+pub mod random;
+pub mod stats;
+pub mod transform;
+pub mod vectorized;
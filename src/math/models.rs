@@ -0,0 +1,144 @@
+//! Simple trainable models built on the matrix and optimization modules.
+
+use crate::math::matrix::matrix::Matrix;
+use crate::math::optimize::gradient_descent;
+
+/// Binary logistic regression, `P(y | x) = sigmoid(w . x + b)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogisticRegression {
+    pub weights: Vec<f64>,
+    pub bias: f64,
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+// log(1 + e^z) without overflow for large |z|
+fn softplus(z: f64) -> f64 {
+    z.max(0.0) + (-z.abs()).exp().ln_1p()
+}
+
+impl LogisticRegression {
+    /// Trains on the rows of `x` with batch gradient descent on the mean
+    /// log-loss, running `iters` steps of size `lr`.
+    pub fn fit(x: &Matrix, y: &[bool], lr: f64, iters: usize) -> Self {
+        assert_eq!(x.rows, y.len(), "Need one label per row");
+
+        let (n, d) = (x.rows, x.cols);
+        // parameters as a (d + 1) x 1 column, the bias last
+        let logit = |p: &Matrix, i: usize| -> f64 {
+            (0..d).map(|j| x.get(i, j) * p.data[j]).sum::<f64>() + p.data[d]
+        };
+
+        let loss = |p: &Matrix| -> f64 {
+            (0..n)
+                .map(|i| {
+                    let z = logit(p, i);
+                    softplus(z) - if y[i] { z } else { 0.0 }
+                })
+                .sum::<f64>()
+                / n.max(1) as f64
+        };
+
+        let grad = |p: &Matrix| -> Matrix {
+            let mut g = Matrix::zeros(d + 1, 1);
+            for (i, &label) in y.iter().enumerate() {
+                let err = sigmoid(logit(p, i)) - if label { 1.0 } else { 0.0 };
+                for j in 0..d {
+                    g.data[j] += err * x.get(i, j);
+                }
+                g.data[d] += err;
+            }
+            for v in &mut g.data {
+                *v /= n.max(1) as f64;
+            }
+            g
+        };
+
+        let params = gradient_descent(loss, grad, Matrix::zeros(d + 1, 1), lr, iters);
+
+        Self {
+            weights: params.data[..d].to_vec(),
+            bias: params.data[d],
+        }
+    }
+
+    /// Probability of the positive class for each row of `x`.
+    pub fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        assert_eq!(
+            x.cols,
+            self.weights.len(),
+            "Feature count must match the trained model"
+        );
+
+        (0..x.rows)
+            .map(|i| {
+                let z: f64 = (0..x.cols).map(|j| x.get(i, j) * self.weights[j]).sum();
+                sigmoid(z + self.bias)
+            })
+            .collect()
+    }
+
+    /// Predicts the positive class where its probability is at least 0.5.
+    pub fn predict(&self, x: &Matrix) -> Vec<bool> {
+        self.predict_proba(x)
+            .into_iter()
+            .map(|p| p >= 0.5)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::random::Rng;
+
+    // points on either side of the line x0 + 2 x1 = 1, kept away from it
+    fn separable(n: usize, rng: &mut Rng) -> (Matrix, Vec<bool>) {
+        let mut data = Vec::new();
+        let mut labels = Vec::new();
+        while labels.len() < n {
+            let (a, b) = (rng.next_f64() * 4.0 - 2.0, rng.next_f64() * 4.0 - 2.0);
+            let side = a + 2.0 * b - 1.0;
+            if side.abs() < 0.2 {
+                continue;
+            }
+            data.extend([a, b]);
+            labels.push(side > 0.0);
+        }
+        (Matrix::new(n, 2, data), labels)
+    }
+
+    #[test]
+    fn test_separable_data() {
+        let mut rng = Rng::new(5);
+        let (x, y) = separable(200, &mut rng);
+        let model = LogisticRegression::fit(&x, &y, 0.5, 2000);
+
+        let (x_test, y_test) = separable(200, &mut rng);
+        let correct = model
+            .predict(&x_test)
+            .iter()
+            .zip(&y_test)
+            .filter(|(p, t)| p == t)
+            .count();
+        assert!(correct >= 196, "{correct}/200 correct");
+
+        // the learned boundary points the same way as the true one
+        assert!(model.weights[0] > 0.0 && model.weights[1] > model.weights[0]);
+        assert!(model.bias < 0.0);
+    }
+
+    #[test]
+    fn test_probabilities_in_range() {
+        let x = Matrix::new(4, 1, vec![-3.0, -1.0, 1.0, 3.0]);
+        let y = [false, false, true, true];
+        let model = LogisticRegression::fit(&x, &y, 1.0, 500);
+        let p = model.predict_proba(&x);
+
+        assert!(p.iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!(p.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(model.predict(&x), y);
+    }
+}
@@ -0,0 +1,195 @@
+use std::f64::consts::SQRT_2;
+
+/// Ken Perlin's improved gradient noise, seeded through a permutation table.
+pub struct PerlinNoise {
+    perm: [u8; 512],
+}
+
+impl PerlinNoise {
+    pub fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+
+        // splitmix64 driving a Fisher-Yates shuffle
+        let mut state = seed;
+        for i in (1..256).rev() {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^= z >> 31;
+            table.swap(i, (z % (i as u64 + 1)) as usize);
+        }
+
+        Self {
+            perm: std::array::from_fn(|i| table[i & 255]),
+        }
+    }
+
+    /// 2D noise in [-1, 1]; zero at every integer lattice point.
+    pub fn noise2d(&self, x: f64, y: f64) -> f64 {
+        let (xi, xf) = split(x);
+        let (yi, yf) = split(y);
+        let (u, v) = (fade(xf), fade(yf));
+        let p = &self.perm;
+
+        let aa = p[p[xi] as usize + yi] as usize;
+        let ab = p[p[xi] as usize + yi + 1] as usize;
+        let ba = p[p[xi + 1] as usize + yi] as usize;
+        let bb = p[p[xi + 1] as usize + yi + 1] as usize;
+
+        let x1 = lerp(grad2(aa, xf, yf), grad2(ba, xf - 1.0, yf), u);
+        let x2 = lerp(grad2(ab, xf, yf - 1.0), grad2(bb, xf - 1.0, yf - 1.0), u);
+
+        (lerp(x1, x2, v) * SQRT_2).clamp(-1.0, 1.0)
+    }
+
+    /// 3D noise in [-1, 1]; zero at every integer lattice point.
+    pub fn noise3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (xi, xf) = split(x);
+        let (yi, yf) = split(y);
+        let (zi, zf) = split(z);
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+        let p = &self.perm;
+
+        let a = p[xi] as usize + yi;
+        let aa = p[a] as usize + zi;
+        let ab = p[a + 1] as usize + zi;
+        let b = p[xi + 1] as usize + yi;
+        let ba = p[b] as usize + zi;
+        let bb = p[b + 1] as usize + zi;
+
+        let g = |h: usize, dx: f64, dy: f64, dz: f64| grad3(p[h], xf - dx, yf - dy, zf - dz);
+
+        let value = lerp(
+            lerp(
+                lerp(g(aa, 0.0, 0.0, 0.0), g(ba, 1.0, 0.0, 0.0), u),
+                lerp(g(ab, 0.0, 1.0, 0.0), g(bb, 1.0, 1.0, 0.0), u),
+                v,
+            ),
+            lerp(
+                lerp(g(aa + 1, 0.0, 0.0, 1.0), g(ba + 1, 1.0, 0.0, 1.0), u),
+                lerp(g(ab + 1, 0.0, 1.0, 1.0), g(bb + 1, 1.0, 1.0, 1.0), u),
+                v,
+            ),
+            w,
+        );
+
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Fractal Brownian motion: `octaves` layers of 2D noise, each at
+    /// `lacunarity` times the frequency and `gain` times the amplitude of the
+    /// last. Normalized so the result stays in [-1, 1].
+    pub fn fbm(&self, x: f64, y: f64, octaves: u32, lacunarity: f64, gain: f64) -> f64 {
+        let mut sum = 0.0;
+        let mut norm = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+
+        for _ in 0..octaves {
+            sum += amplitude * self.noise2d(x * frequency, y * frequency);
+            norm += amplitude;
+            amplitude *= gain;
+            frequency *= lacunarity;
+        }
+
+        if norm == 0.0 {
+            0.0
+        } else {
+            sum / norm
+        }
+    }
+}
+
+fn split(v: f64) -> (usize, f64) {
+    let floor = v.floor();
+    ((floor as i64 & 255) as usize, v - floor)
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn grad2(hash: usize, x: f64, y: f64) -> f64 {
+    const D: f64 = std::f64::consts::FRAC_1_SQRT_2;
+    match hash & 7 {
+        0 => x,
+        1 => -x,
+        2 => y,
+        3 => -y,
+        4 => D * (x + y),
+        5 => D * (-x + y),
+        6 => D * (x - y),
+        _ => D * (-x - y),
+    }
+}
+
+fn grad3(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> impl Iterator<Item = (f64, f64, f64)> {
+        (0..2000).map(|i| {
+            let t = i as f64;
+            (t * 0.137 - 80.0, t * 0.291 + 3.3, t * 0.053 - 11.7)
+        })
+    }
+
+    #[test]
+    fn test_deterministic_for_seed() {
+        let a = PerlinNoise::new(42);
+        let b = PerlinNoise::new(42);
+        let c = PerlinNoise::new(43);
+
+        let mut differs = false;
+        for (x, y, z) in samples() {
+            assert_eq!(a.noise2d(x, y), b.noise2d(x, y));
+            assert_eq!(a.noise3d(x, y, z), b.noise3d(x, y, z));
+            differs |= a.noise3d(x, y, z) != c.noise3d(x, y, z);
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_within_range() {
+        let noise = PerlinNoise::new(7);
+
+        for (x, y, z) in samples() {
+            assert!((-1.0..=1.0).contains(&noise.noise2d(x, y)));
+            assert!((-1.0..=1.0).contains(&noise.noise3d(x, y, z)));
+            assert!((-1.0..=1.0).contains(&noise.fbm(x, y, 5, 2.0, 0.5)));
+        }
+
+        assert_eq!(noise.noise2d(3.0, -4.0), 0.0);
+        assert_eq!(noise.noise3d(1.0, 2.0, 3.0), 0.0);
+    }
+
+    #[test]
+    fn test_continuous() {
+        let noise = PerlinNoise::new(1234);
+        let h = 1e-4;
+
+        for (x, y, z) in samples() {
+            assert!((noise.noise2d(x + h, y) - noise.noise2d(x, y)).abs() < 1e-2);
+            assert!((noise.noise2d(x, y + h) - noise.noise2d(x, y)).abs() < 1e-2);
+            assert!((noise.noise3d(x, y, z + h) - noise.noise3d(x, y, z)).abs() < 1e-2);
+        }
+    }
+}
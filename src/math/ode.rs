@@ -0,0 +1,299 @@
+//! Explicit Runge-Kutta integrators for `y' = f(t, y)`.
+
+use std::error::Error;
+use std::fmt;
+
+/// `rk45` gave up before reaching `t1`. `path` holds the steps accepted so
+/// far, ending at the time the integrator stalled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepFailure {
+    pub path: Vec<(f64, Vec<f64>)>,
+}
+
+impl fmt::Display for StepFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = self.path.last().map_or(f64::NAN, |(t, _)| *t);
+        write!(f, "RK45 stalled at t = {t}")
+    }
+}
+
+impl Error for StepFailure {}
+
+fn axpy(y: &[f64], h: f64, terms: &[(f64, &[f64])]) -> Vec<f64> {
+    let mut out = y.to_vec();
+    for &(c, k) in terms {
+        for (o, ki) in out.iter_mut().zip(k) {
+            *o += h * c * ki;
+        }
+    }
+    out
+}
+
+/// Classic fourth-order Runge-Kutta with `steps` fixed steps from `t0` to `t1`.
+///
+/// Returns `steps + 1` states, starting with `y0` and ending at `t1`.
+pub fn rk4<F>(f: F, y0: &[f64], t0: f64, t1: f64, steps: usize) -> Vec<Vec<f64>>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    assert!(steps > 0, "RK4 requires at least one step");
+
+    let h = (t1 - t0) / steps as f64;
+    let mut out = Vec::with_capacity(steps + 1);
+    let mut y = y0.to_vec();
+    out.push(y.clone());
+
+    for i in 0..steps {
+        let t = t0 + i as f64 * h;
+        let k1 = f(t, &y);
+        let k2 = f(t + h / 2.0, &axpy(&y, h, &[(0.5, &k1)]));
+        let k3 = f(t + h / 2.0, &axpy(&y, h, &[(0.5, &k2)]));
+        let k4 = f(t + h, &axpy(&y, h, &[(1.0, &k3)]));
+
+        y = axpy(
+            &y,
+            h,
+            &[
+                (1.0 / 6.0, &k1),
+                (1.0 / 3.0, &k2),
+                (1.0 / 3.0, &k3),
+                (1.0 / 6.0, &k4),
+            ],
+        );
+        out.push(y.clone());
+    }
+
+    out
+}
+
+/// Adaptive Dormand-Prince RK45 from `t0` to `t1`.
+///
+/// The step size is adjusted so the estimated local error of each accepted
+/// step stays below `tol` (max norm). Returns every accepted `(t, y)` pair,
+/// starting with `(t0, y0)` and ending at `t1`.
+///
+/// A non-finite error estimate rejects the step. Fails once the step size
+/// drops to the rounding level of `t`, or after `MAX_ATTEMPTS` tried steps.
+pub fn rk45<F>(
+    f: F,
+    y0: &[f64],
+    t0: f64,
+    t1: f64,
+    tol: f64,
+) -> Result<Vec<(f64, Vec<f64>)>, StepFailure>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    const C: [f64; 6] = [1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+    const B5: [f64; 7] = [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+        0.0,
+    ];
+    const B4: [f64; 7] = [
+        5179.0 / 57600.0,
+        0.0,
+        7571.0 / 16695.0,
+        393.0 / 640.0,
+        -92097.0 / 339200.0,
+        187.0 / 2100.0,
+        1.0 / 40.0,
+    ];
+
+    const MAX_ATTEMPTS: usize = 1_000_000;
+
+    assert!(tol > 0.0, "Tolerance must be positive");
+
+    let span = t1 - t0;
+    let dir = span.signum();
+    let mut t = t0;
+    let mut y = y0.to_vec();
+    let mut h = span / 100.0;
+    let mut out = vec![(t, y.clone())];
+
+    if span == 0.0 {
+        return Ok(out);
+    }
+
+    let mut k1 = f(t, &y);
+    let mut attempts = 0;
+
+    while (t1 - t) * dir > 0.0 {
+        let h_min = 16.0 * f64::EPSILON * t.abs().max(t1.abs());
+        attempts += 1;
+        if h.abs() < h_min || attempts > MAX_ATTEMPTS {
+            return Err(StepFailure { path: out });
+        }
+
+        let last = (t + h - t1) * dir >= 0.0;
+        if last {
+            h = t1 - t;
+        }
+
+        let k2 = f(t + C[0] * h, &axpy(&y, h, &[(1.0 / 5.0, &k1)]));
+        let k3 = f(
+            t + C[1] * h,
+            &axpy(&y, h, &[(3.0 / 40.0, &k1), (9.0 / 40.0, &k2)]),
+        );
+        let k4 = f(
+            t + C[2] * h,
+            &axpy(
+                &y,
+                h,
+                &[(44.0 / 45.0, &k1), (-56.0 / 15.0, &k2), (32.0 / 9.0, &k3)],
+            ),
+        );
+        let k5 = f(
+            t + C[3] * h,
+            &axpy(
+                &y,
+                h,
+                &[
+                    (19372.0 / 6561.0, &k1),
+                    (-25360.0 / 2187.0, &k2),
+                    (64448.0 / 6561.0, &k3),
+                    (-212.0 / 729.0, &k4),
+                ],
+            ),
+        );
+        let k6 = f(
+            t + C[4] * h,
+            &axpy(
+                &y,
+                h,
+                &[
+                    (9017.0 / 3168.0, &k1),
+                    (-355.0 / 33.0, &k2),
+                    (46732.0 / 5247.0, &k3),
+                    (49.0 / 176.0, &k4),
+                    (-5103.0 / 18656.0, &k5),
+                ],
+            ),
+        );
+
+        let ks: [&[f64]; 6] = [&k1, &k2, &k3, &k4, &k5, &k6];
+        let terms: Vec<(f64, &[f64])> = B5.iter().zip(ks).map(|(&b, k)| (b, k)).collect();
+        let y5 = axpy(&y, h, &terms);
+        let k7 = f(t + C[5] * h, &y5);
+
+        let err = (0..y.len())
+            .map(|i| {
+                let e: f64 = (0..6).map(|j| (B5[j] - B4[j]) * ks[j][i]).sum::<f64>()
+                    + (B5[6] - B4[6]) * k7[i];
+                (h * e).abs()
+            })
+            // `f64::max` would drop a NaN, so poison the estimate instead
+            .fold(0.0, |acc: f64, e| {
+                if e.is_nan() {
+                    f64::INFINITY
+                } else {
+                    acc.max(e)
+                }
+            });
+        let err = if y5.iter().all(|v| v.is_finite()) {
+            err
+        } else {
+            f64::INFINITY
+        };
+
+        if err <= tol {
+            t = if last { t1 } else { t + h };
+            y = y5;
+            k1 = k7; // first-same-as-last
+            out.push((t, y.clone()));
+        }
+
+        let factor = if !err.is_finite() {
+            0.2
+        } else if err == 0.0 {
+            5.0
+        } else {
+            (0.9 * (tol / err).powf(0.2)).clamp(0.2, 5.0)
+        };
+        h *= factor;
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn exponential(_t: f64, y: &[f64]) -> Vec<f64> {
+        vec![y[0]]
+    }
+
+    fn oscillator(_t: f64, y: &[f64]) -> Vec<f64> {
+        vec![y[1], -y[0]]
+    }
+
+    #[test]
+    fn test_rk4_exponential() {
+        let path = rk4(exponential, &[1.0], 0.0, 1.0, 100);
+
+        assert_eq!(path.len(), 101);
+        assert_eq!(path[0], vec![1.0]);
+        assert!((path[100][0] - 1f64.exp()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_rk4_harmonic_oscillator() {
+        let path = rk4(oscillator, &[1.0, 0.0], 0.0, 2.0 * PI, 1000);
+        let end = path.last().unwrap();
+
+        assert!((end[0] - 1.0).abs() < 1e-9);
+        assert!(end[1].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rk45_exponential() {
+        let path = rk45(exponential, &[1.0], 0.0, 2.0, 1e-10).unwrap();
+        let (t, y) = path.last().unwrap();
+
+        assert_eq!(*t, 2.0);
+        assert!((y[0] - 2f64.exp()).abs() < 1e-7);
+        for (t, y) in &path {
+            assert!((y[0] - t.exp()).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_rk45_harmonic_oscillator() {
+        let path = rk45(oscillator, &[0.0, 1.0], 0.0, 10.0, 1e-10).unwrap();
+
+        for (t, y) in &path {
+            assert!((y[0] - t.sin()).abs() < 1e-7);
+            assert!((y[1] - t.cos()).abs() < 1e-7);
+        }
+        assert_eq!(path.last().unwrap().0, 10.0);
+    }
+
+    #[test]
+    fn test_rk45_backwards() {
+        let path = rk45(exponential, &[1.0], 0.0, -1.0, 1e-10).unwrap();
+        let (t, y) = path.last().unwrap();
+
+        assert_eq!(*t, -1.0);
+        assert!((y[0] - (-1f64).exp()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_rk45_gives_up_instead_of_looping() {
+        // a tolerance below rounding noise can never be met
+        let err = rk45(exponential, &[1.0], 0.0, 1.0, 1e-300).unwrap_err();
+        assert!(err.path.last().unwrap().0 < 1.0);
+
+        // NaN derivatives reject every step until the step size underflows
+        let nan_after_half = |t: f64, y: &[f64]| vec![if t > 0.5 { f64::NAN } else { y[0] }];
+        let err = rk45(nan_after_half, &[1.0], 0.0, 1.0, 1e-8).unwrap_err();
+        let (t, _) = *err.path.last().unwrap();
+        assert!(t > 0.4 && t <= 0.5);
+        assert!(err.to_string().starts_with("RK45 stalled at t = 0."));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::math::matrix::matrix::Matrix;
+use crate::math::matrix::ops::{add, scalar_mul, sub};
+
+/// Plain gradient descent: `x <- x - lr * grad(x)` for `iters` steps.
+///
+/// Returns the iterate with the lowest `f` seen, so an overly large `lr`
+/// that starts to diverge never makes the result worse than `x0`.
+pub fn gradient_descent<F, G>(f: F, grad: G, x0: Matrix, lr: f64, iters: usize) -> Matrix
+where
+    F: Fn(&Matrix) -> f64,
+    G: Fn(&Matrix) -> Matrix,
+{
+    gradient_descent_momentum(f, grad, x0, lr, 0.0, iters)
+}
+
+/// Gradient descent with classical momentum:
+/// `v <- momentum * v - lr * grad(x)`, `x <- x + v`.
+pub fn gradient_descent_momentum<F, G>(
+    f: F,
+    grad: G,
+    x0: Matrix,
+    lr: f64,
+    momentum: f64,
+    iters: usize,
+) -> Matrix
+where
+    F: Fn(&Matrix) -> f64,
+    G: Fn(&Matrix) -> Matrix,
+{
+    let mut best_val = f(&x0);
+    let mut best = x0.clone();
+    let mut velocity = Matrix::zeros(x0.rows, x0.cols);
+    let mut x = x0;
+
+    for _ in 0..iters {
+        let g = grad(&x);
+        velocity = sub(&scalar_mul(&velocity, momentum), &scalar_mul(&g, lr))
+            .expect("gradient must have the same shape as x");
+        x = add(&x, &velocity).expect("gradient must have the same shape as x");
+
+        let val = f(&x);
+        if val < best_val {
+            best_val = val;
+            best = x.clone();
+        }
+    }
+
+    best
+}
+
+/// Nelder-Mead simplex minimization of `f` starting from `initial`.
+///
+/// Uses the standard reflection (1), expansion (2), contraction (1/2) and
+/// shrink (1/2) coefficients, and stops after `iters` iterations or once the
+/// spread of `f` across the simplex drops below `tol`.
+pub fn nelder_mead<F>(f: F, initial: &[f64], iters: usize, tol: f64) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    const ALPHA: f64 = 1.0;
+    const GAMMA: f64 = 2.0;
+    const RHO: f64 = 0.5;
+    const SIGMA: f64 = 0.5;
+
+    let n = initial.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((initial.to_vec(), f(initial)));
+    for i in 0..n {
+        let mut v = initial.to_vec();
+        v[i] = if v[i] != 0.0 { v[i] * 1.05 } else { 0.00025 };
+        let fv = f(&v);
+        simplex.push((v, fv));
+    }
+
+    // centroid + t * (p - centroid)
+    let toward = |centroid: &[f64], p: &[f64], t: f64| -> Vec<f64> {
+        centroid
+            .iter()
+            .zip(p)
+            .map(|(c, x)| c + t * (x - c))
+            .collect()
+    };
+
+    for _ in 0..iters {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        if simplex[n].1 - simplex[0].1 < tol {
+            break;
+        }
+
+        let mut centroid = vec![0.0; n];
+        for (v, _) in &simplex[..n] {
+            for (c, x) in centroid.iter_mut().zip(v) {
+                *c += x / n as f64;
+            }
+        }
+
+        let worst = simplex[n].0.clone();
+        let reflected = toward(&centroid, &worst, -ALPHA);
+        let fr = f(&reflected);
+
+        if fr < simplex[0].1 {
+            let expanded = toward(&centroid, &worst, -GAMMA);
+            let fe = f(&expanded);
+            simplex[n] = if fe < fr {
+                (expanded, fe)
+            } else {
+                (reflected, fr)
+            };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (reflected, fr);
+        } else {
+            let contracted = if fr < simplex[n].1 {
+                toward(&centroid, &reflected, RHO)
+            } else {
+                toward(&centroid, &worst, RHO)
+            };
+            let fc = f(&contracted);
+
+            if fc < fr.min(simplex[n].1) {
+                simplex[n] = (contracted, fc);
+            } else {
+                let best = simplex[0].0.clone();
+                for (v, fv) in simplex.iter_mut().skip(1) {
+                    for (x, b) in v.iter_mut().zip(&best) {
+                        *x = b + SIGMA * (*x - b);
+                    }
+                    *fv = f(v);
+                }
+            }
+        }
+    }
+
+    simplex
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(v, _)| v)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // f(x, y) = (x - 3)^2 + 10 (y + 1)^2, minimum at (3, -1)
+    fn quadratic(x: &Matrix) -> f64 {
+        (x.get(0, 0) - 3.0).powi(2) + 10.0 * (x.get(1, 0) + 1.0).powi(2)
+    }
+
+    fn quadratic_grad(x: &Matrix) -> Matrix {
+        Matrix::new(
+            2,
+            1,
+            vec![2.0 * (x.get(0, 0) - 3.0), 20.0 * (x.get(1, 0) + 1.0)],
+        )
+    }
+
+    #[test]
+    fn test_converges_to_minimum() {
+        let x0 = Matrix::new(2, 1, vec![-5.0, 4.0]);
+        let x = gradient_descent(quadratic, quadratic_grad, x0, 0.05, 500);
+
+        assert!((x.get(0, 0) - 3.0).abs() < 1e-6);
+        assert!((x.get(1, 0) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_momentum_converges_to_minimum() {
+        let x0 = Matrix::new(2, 1, vec![-5.0, 4.0]);
+        let x = gradient_descent_momentum(quadratic, quadratic_grad, x0, 0.01, 0.9, 500);
+
+        assert!((x.get(0, 0) - 3.0).abs() < 1e-6);
+        assert!((x.get(1, 0) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_divergent_step_keeps_best() {
+        let x0 = Matrix::new(2, 1, vec![0.0, 0.0]);
+        let x = gradient_descent(quadratic, quadratic_grad, x0.clone(), 1.0, 20);
+
+        assert!(quadratic(&x) <= quadratic(&x0));
+    }
+
+    #[test]
+    fn test_nelder_mead_rosenbrock() {
+        let rosenbrock = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+        let x = nelder_mead(rosenbrock, &[-1.2, 1.0], 5000, 1e-14);
+
+        assert!((x[0] - 1.0).abs() < 1e-3, "x = {x:?}");
+        assert!((x[1] - 1.0).abs() < 1e-3, "x = {x:?}");
+    }
+
+    #[test]
+    fn test_nelder_mead_from_origin() {
+        let bowl = |p: &[f64]| (p[0] - 2.0).powi(2) + (p[1] + 0.5).powi(2) + p[2].powi(2);
+        let x = nelder_mead(bowl, &[0.0, 0.0, 0.0], 5000, 1e-14);
+
+        assert!((x[0] - 2.0).abs() < 1e-4);
+        assert!((x[1] + 0.5).abs() < 1e-4);
+        assert!(x[2].abs() < 1e-4);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::math::complex::Complex;
+use crate::math::fft::{fft, ifft};
+use std::ops::{Add, Mul};
+
+/// A real polynomial, coefficients stored low degree first so `coeffs[k]`
+/// multiplies `x^k`. Trailing zero coefficients are trimmed, so the zero
+/// polynomial has no coefficients.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polynomial {
+    pub coeffs: Vec<f64>,
+}
+
+impl Polynomial {
+    pub fn new(mut coeffs: Vec<f64>) -> Self {
+        while coeffs.last() == Some(&0.0) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    /// Degree of the polynomial; the zero polynomial reports 0.
+    pub fn degree(&self) -> usize {
+        self.coeffs.len().saturating_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Evaluates at `x` with Horner's method.
+    pub fn eval(&self, x: f64) -> f64 {
+        self.coeffs.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Polynomial {
+        Polynomial::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(k, &c)| k as f64 * c)
+                .collect(),
+        )
+    }
+
+    /// Product by FFT convolution in O(n log n), zero-padding both operands
+    /// to the next power of two. Agrees with `*` up to rounding, which is
+    /// worth it once both factors have more than a few dozen coefficients.
+    pub fn mul_fft(&self, rhs: &Polynomial) -> Polynomial {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::default();
+        }
+
+        let len = self.coeffs.len() + rhs.coeffs.len() - 1;
+        let size = len.next_power_of_two();
+        let spectrum = |coeffs: &[f64]| {
+            let mut data = vec![Complex::zero(); size];
+            for (slot, &c) in data.iter_mut().zip(coeffs) {
+                *slot = Complex::from_real(c);
+            }
+            fft(&mut data);
+            data
+        };
+
+        let mut product = spectrum(&self.coeffs);
+        for (p, q) in product.iter_mut().zip(spectrum(&rhs.coeffs)) {
+            *p *= q;
+        }
+        ifft(&mut product);
+
+        Polynomial::new(product[..len].iter().map(|c| c.re).collect())
+    }
+
+    /// Real roots in ascending order, repeated roots listed once per
+    /// multiplicity. Only degrees up to 2 are supported; constants have no
+    /// roots.
+    pub fn roots(&self) -> Vec<f64> {
+        match self.coeffs[..] {
+            [] | [_] => Vec::new(),
+            [c, b] => vec![-c / b],
+            [c, b, a] => {
+                let disc = b * b - 4.0 * a * c;
+                if disc < 0.0 {
+                    return Vec::new();
+                }
+                // avoid cancellation between -b and the square root
+                let q = -0.5 * (b + b.signum() * disc.sqrt());
+                let mut roots = if q == 0.0 {
+                    vec![0.0, 0.0]
+                } else {
+                    vec![q / a, c / q]
+                };
+                roots.sort_by(f64::total_cmp);
+                roots
+            }
+            _ => panic!("Roots are only supported up to degree 2"),
+        }
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: &Polynomial) -> Polynomial {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        Polynomial::new(
+            (0..len)
+                .map(|k| self.coeffs.get(k).unwrap_or(&0.0) + rhs.coeffs.get(k).unwrap_or(&0.0))
+                .collect(),
+        )
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: &Polynomial) -> Polynomial {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::default();
+        }
+
+        let mut coeffs = vec![0.0; self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] += a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl Add for Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: Polynomial) -> Polynomial {
+        &self + &rhs
+    }
+}
+
+impl Mul for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: Polynomial) -> Polynomial {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+
+        assert_eq!(p.eval(2.0), 17.0);
+        assert_eq!(p.eval(0.0), 1.0);
+        assert_eq!(Polynomial::default().eval(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_add_and_mul() {
+        // (1 + x)(1 - x) = 1 - x^2
+        let a = Polynomial::new(vec![1.0, 1.0]);
+        let b = Polynomial::new(vec![1.0, -1.0]);
+
+        assert_eq!(&a * &b, Polynomial::new(vec![1.0, 0.0, -1.0]));
+        // the x terms cancel and the result is trimmed
+        assert_eq!(&a + &b, Polynomial::new(vec![2.0]));
+        assert_eq!((&a + &b).degree(), 0);
+
+        // (2 + 3x)(1 + x + x^2) = 2 + 5x + 5x^2 + 3x^3
+        let c = Polynomial::new(vec![2.0, 3.0]) * Polynomial::new(vec![1.0, 1.0, 1.0]);
+        assert_eq!(c.coeffs, vec![2.0, 5.0, 5.0, 3.0]);
+        assert!((&c * &Polynomial::default()).is_zero());
+    }
+
+    #[test]
+    fn test_mul_fft_matches_naive() {
+        use crate::math::random::Rng;
+
+        let mut rng = Rng::new(17);
+        let mut random =
+            |len: usize| Polynomial::new((0..len).map(|_| rng.next_f64() * 2.0 - 1.0).collect());
+
+        for (m, n) in [(1, 1), (1, 7), (3, 5), (64, 64), (100, 37), (513, 300)] {
+            let (a, b) = (random(m), random(n));
+            let naive = &a * &b;
+            let fast = a.mul_fft(&b);
+
+            assert_eq!(fast.coeffs.len(), naive.coeffs.len());
+            for (f, w) in fast.coeffs.iter().zip(&naive.coeffs) {
+                assert!((f - w).abs() < 1e-10, "{m}x{n}: {f} vs {w}");
+            }
+        }
+        assert!(random(4).mul_fft(&Polynomial::default()).is_zero());
+    }
+
+    #[test]
+    fn test_derivative() {
+        let p = Polynomial::new(vec![2.0, 5.0, 5.0, 3.0]);
+
+        assert_eq!(p.derivative().coeffs, vec![5.0, 10.0, 9.0]);
+        assert!(Polynomial::new(vec![4.0]).derivative().is_zero());
+    }
+
+    #[test]
+    fn test_roots() {
+        // x^2 - 3x + 2 = (x - 1)(x - 2)
+        assert_eq!(
+            Polynomial::new(vec![2.0, -3.0, 1.0]).roots(),
+            vec![1.0, 2.0]
+        );
+        // (x + 3)^2
+        assert_eq!(
+            Polynomial::new(vec![9.0, 6.0, 1.0]).roots(),
+            vec![-3.0, -3.0]
+        );
+        // x^2 + 1 has no real roots
+        assert!(Polynomial::new(vec![1.0, 0.0, 1.0]).roots().is_empty());
+        assert_eq!(Polynomial::new(vec![0.0, 0.0, 2.0]).roots(), vec![0.0, 0.0]);
+        assert_eq!(Polynomial::new(vec![3.0, -2.0]).roots(), vec![1.5]);
+
+        // tiny c: the stable form keeps the small root accurate
+        let roots = Polynomial::new(vec![1e-10, 1e5, 1.0]).roots();
+        assert!((roots[1] + 1e-15).abs() < 1e-25);
+    }
+
+    #[test]
+    #[should_panic(expected = "Roots are only supported up to degree 2")]
+    fn test_roots_cubic() {
+        Polynomial::new(vec![1.0, 0.0, 0.0, 1.0]).roots();
+    }
+}
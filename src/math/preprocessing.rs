@@ -0,0 +1,295 @@
+//! Feature preprocessing for rows-as-samples data matrices.
+
+use crate::math::matrix::matrix::Matrix;
+use crate::math::matrix::utils::error::Error;
+use crate::math::random::Rng;
+use crate::math::stats::RunningMatrixStats;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Standardizes each column to zero mean and unit variance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardScaler {
+    pub mean: Vec<f64>,
+    /// Population standard deviation per column; zero-variance columns store
+    /// 1.0 so they transform to all zeros instead of NaN.
+    pub scale: Vec<f64>,
+}
+
+impl StandardScaler {
+    /// Learns per-column mean and standard deviation from `data`.
+    pub fn fit(data: &Matrix) -> Self {
+        let mut stats = RunningMatrixStats::new(data.cols);
+        stats.push_matrix(data);
+
+        let scale = stats
+            .std_dev()
+            .into_iter()
+            .map(|s| if s > f64::EPSILON { s } else { 1.0 })
+            .collect();
+
+        Self {
+            mean: stats.mean().to_vec(),
+            scale,
+        }
+    }
+
+    pub fn fit_transform(data: &Matrix) -> (Self, Matrix) {
+        let scaler = Self::fit(data);
+        let scaled = scaler.map(data, |x, mean, scale| (x - mean) / scale);
+        (scaler, scaled)
+    }
+
+    /// Applies `(x - mean) / scale` column-wise.
+    pub fn transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        self.check(data)?;
+        Ok(self.map(data, |x, mean, scale| (x - mean) / scale))
+    }
+
+    /// Undoes `transform`: `x * scale + mean` column-wise.
+    pub fn inverse_transform(&self, data: &Matrix) -> Result<Matrix, Error> {
+        self.check(data)?;
+        Ok(self.map(data, |x, mean, scale| x * scale + mean))
+    }
+
+    fn check(&self, data: &Matrix) -> Result<(), Error> {
+        if data.cols != self.mean.len() {
+            return Err(Error::MatrixSizeMismatch);
+        }
+        Ok(())
+    }
+
+    fn map(&self, data: &Matrix, f: impl Fn(f64, f64, f64) -> f64) -> Matrix {
+        let mut result = Matrix::zeros(data.rows, data.cols);
+        for i in 0..data.rows {
+            for j in 0..data.cols {
+                result.set(i, j, f(data.get(i, j), self.mean[j], self.scale[j]));
+            }
+        }
+        result
+    }
+}
+
+/// One row per label with a single 1.0 in that label's column.
+pub fn one_hot(labels: &[usize], num_classes: usize) -> Matrix {
+    let mut result = Matrix::zeros(labels.len(), num_classes);
+    for (i, &label) in labels.iter().enumerate() {
+        assert!(
+            label < num_classes,
+            "Label {label} out of range for {num_classes} classes"
+        );
+        result.set(i, label, 1.0);
+    }
+    result
+}
+
+/// Maps arbitrary categories to dense indices `0..num_classes`, in the order
+/// they were first seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelEncoder<T: Eq + Hash> {
+    classes: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash + Clone> LabelEncoder<T> {
+    pub fn fit(categories: &[T]) -> Self {
+        let mut classes = Vec::new();
+        let mut index = HashMap::new();
+        for category in categories {
+            if !index.contains_key(category) {
+                index.insert(category.clone(), classes.len());
+                classes.push(category.clone());
+            }
+        }
+        Self { classes, index }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn classes(&self) -> &[T] {
+        &self.classes
+    }
+
+    pub fn encode(&self, category: &T) -> Option<usize> {
+        self.index.get(category).copied()
+    }
+
+    pub fn decode(&self, label: usize) -> Option<&T> {
+        self.classes.get(label)
+    }
+
+    /// Encodes every category, or `None` if any was not seen during `fit`.
+    pub fn transform(&self, categories: &[T]) -> Option<Vec<usize>> {
+        categories.iter().map(|c| self.encode(c)).collect()
+    }
+
+    /// Decodes every label, or `None` if any is out of range.
+    pub fn inverse_transform(&self, labels: &[usize]) -> Option<Vec<T>> {
+        labels.iter().map(|&l| self.decode(l).cloned()).collect()
+    }
+}
+
+/// Shuffles the rows of `data` (and `labels` with them) and splits off
+/// `round(rows * test_fraction)` of them as the test set.
+///
+/// Returns `(train_data, test_data, train_labels, test_labels)`.
+pub fn train_test_split(
+    data: &Matrix,
+    labels: &[usize],
+    test_fraction: f64,
+    rng: &mut Rng,
+) -> (Matrix, Matrix, Vec<usize>, Vec<usize>) {
+    assert_eq!(data.rows, labels.len(), "Need one label per row");
+    assert!(
+        (0.0..=1.0).contains(&test_fraction),
+        "Test fraction must be in [0, 1]"
+    );
+
+    let mut indices: Vec<usize> = (0..data.rows).collect();
+    rng.shuffle(&mut indices);
+
+    let n_test = (data.rows as f64 * test_fraction).round() as usize;
+    let (test_idx, train_idx) = indices.split_at(n_test);
+
+    let take = |idx: &[usize]| {
+        let mut rows = Vec::with_capacity(idx.len() * data.cols);
+        for &i in idx {
+            rows.extend_from_slice(&data.data[i * data.cols..(i + 1) * data.cols]);
+        }
+        let labels = idx.iter().map(|&i| labels[i]).collect();
+        (Matrix::new(idx.len(), data.cols, rows), labels)
+    };
+
+    let (train_data, train_labels) = take(train_idx);
+    let (test_data, test_labels) = take(test_idx);
+    (train_data, test_data, train_labels, test_labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Matrix {
+        let data = (0..50)
+            .flat_map(|i| {
+                let t = i as f64;
+                [t * 3.0 - 20.0, (t * 0.4).cos() * 100.0, 5.0]
+            })
+            .collect();
+        Matrix::new(50, 3, data)
+    }
+
+    #[test]
+    fn test_fit_transform_standardizes() {
+        let data = dataset();
+        let (scaler, scaled) = StandardScaler::fit_transform(&data);
+
+        for j in 0..2 {
+            let column: Vec<f64> = (0..scaled.rows).map(|i| scaled.get(i, j)).collect();
+            let mean = column.iter().sum::<f64>() / column.len() as f64;
+            let var = column.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / column.len() as f64;
+
+            assert!(mean.abs() < 1e-12);
+            assert!((var - 1.0).abs() < 1e-12);
+        }
+
+        // the constant column maps to zeros rather than NaN
+        assert_eq!(scaler.scale[2], 1.0);
+        assert!((0..scaled.rows).all(|i| scaled.get(i, 2) == 0.0));
+
+        assert_eq!(scaler.transform(&data).unwrap(), scaled);
+    }
+
+    #[test]
+    fn test_inverse_transform_roundtrip() {
+        let data = dataset();
+        let scaler = StandardScaler::fit(&data);
+        let restored = scaler
+            .inverse_transform(&scaler.transform(&data).unwrap())
+            .unwrap();
+
+        for (got, want) in restored.data.iter().zip(&data.data) {
+            assert!((got - want).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_column_mismatch() {
+        let scaler = StandardScaler::fit(&dataset());
+
+        assert!(matches!(
+            scaler.transform(&Matrix::zeros(4, 2)),
+            Err(Error::MatrixSizeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let encoded = one_hot(&[2, 0, 1, 2], 3);
+
+        assert_eq!((encoded.rows, encoded.cols), (4, 3));
+        for i in 0..encoded.rows {
+            let row = &encoded.data[i * 3..(i + 1) * 3];
+            assert_eq!(row.iter().sum::<f64>(), 1.0);
+        }
+        assert_eq!(
+            encoded.data,
+            vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Label 3 out of range for 3 classes")]
+    fn test_one_hot_out_of_range() {
+        one_hot(&[0, 3], 3);
+    }
+
+    #[test]
+    fn test_label_encoder_roundtrip() {
+        let data = ["cat", "dog", "cat", "bird", "dog"];
+        let encoder = LabelEncoder::fit(&data);
+
+        assert_eq!(encoder.num_classes(), 3);
+        assert_eq!(encoder.classes(), &["cat", "dog", "bird"]);
+
+        let labels = encoder.transform(&data).unwrap();
+        assert_eq!(labels, vec![0, 1, 0, 2, 1]);
+        assert_eq!(encoder.inverse_transform(&labels).unwrap(), data);
+
+        assert_eq!(encoder.transform(&["cat", "fish"]), None);
+        assert_eq!(encoder.inverse_transform(&[0, 3]), None);
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        // row i is [i, 2i] with label i, so rows can be traced after shuffling
+        let n = 100;
+        let data = Matrix::new(
+            n,
+            2,
+            (0..n).flat_map(|i| [i as f64, 2.0 * i as f64]).collect(),
+        );
+        let labels: Vec<usize> = (0..n).collect();
+        let mut rng = Rng::new(3);
+
+        let (train, test, train_labels, test_labels) =
+            train_test_split(&data, &labels, 0.25, &mut rng);
+
+        assert_eq!((train.rows, test.rows), (75, 25));
+        assert_eq!((train_labels.len(), test_labels.len()), (75, 25));
+
+        let mut seen = vec![false; n];
+        for (m, labels) in [(&train, &train_labels), (&test, &test_labels)] {
+            for (r, &label) in labels.iter().enumerate() {
+                assert_eq!(m.get(r, 0), label as f64);
+                assert_eq!(m.get(r, 1), 2.0 * label as f64);
+                assert!(!seen[label], "row {label} appears twice");
+                seen[label] = true;
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_ne!(test_labels, (0..25).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::math::geometry::Vec3;
+use crate::math::matrix::matrix::Matrix;
+use std::ops::Mul;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Rotation of `angle` radians about `axis` (need not be unit length).
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (s, c) = (angle / 2.0).sin_cos();
+        Self::new(c, axis.x * s, axis.y * s, axis.z * s)
+    }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let n = self.norm();
+        assert!(n != 0.0, "Cannot normalize a zero quaternion");
+        Self::new(self.w / n, self.x / n, self.y / n, self.z / n)
+    }
+
+    pub fn conjugate(self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Rotates `v` by this (unit) quaternion: q v q*.
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let p = self * Self::new(0.0, v.x, v.y, v.z) * self.conjugate();
+        Vec3::new(p.x, p.y, p.z)
+    }
+
+    pub fn to_rotation_matrix(self) -> Matrix {
+        let Self { w, x, y, z } = self.normalize();
+
+        Matrix::new(
+            3,
+            3,
+            vec![
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        )
+    }
+
+    /// Spherical linear interpolation along the shorter arc.
+    pub fn slerp(self, other: Self, t: f64) -> Self {
+        let a = self.normalize();
+        let mut b = other.normalize();
+        let mut cos = a.dot(b);
+
+        if cos < 0.0 {
+            b = Self::new(-b.w, -b.x, -b.y, -b.z);
+            cos = -cos;
+        }
+
+        // nearly parallel, fall back to lerp to avoid dividing by ~0
+        if cos > 0.9995 {
+            return Self::new(
+                a.w + (b.w - a.w) * t,
+                a.x + (b.x - a.x) * t,
+                a.y + (b.y - a.y) * t,
+                a.z + (b.z - a.z) * t,
+            )
+            .normalize();
+        }
+
+        let theta = cos.acos();
+        let sin = theta.sin();
+        let wa = ((1.0 - t) * theta).sin() / sin;
+        let wb = (t * theta).sin() / sin;
+
+        Self::new(
+            wa * a.w + wb * b.w,
+            wa * a.x + wb * b.x,
+            wa * a.y + wb * b.y,
+            wa * a.z + wb * b.z,
+        )
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::ops;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn approx_q(a: Quaternion, b: Quaternion) -> bool {
+        approx(a.w, b.w) && approx(a.x, b.x) && approx(a.y, b.y) && approx(a.z, b.z)
+    }
+
+    #[test]
+    fn test_rotation_matches_matrix() {
+        let q = Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, -0.5), 1.234);
+        let m = q.to_rotation_matrix();
+        let v = Vec3::new(0.3, -4.0, 2.5);
+
+        let rotated = q.rotate(v);
+        let expected = ops::mul(&m, &Matrix::new(3, 1, vec![v.x, v.y, v.z])).unwrap();
+
+        assert!(approx(rotated.x, expected.get(0, 0)));
+        assert!(approx(rotated.y, expected.get(1, 0)));
+        assert!(approx(rotated.z, expected.get(2, 0)));
+    }
+
+    #[test]
+    fn test_quarter_turn_about_z() {
+        let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+        let v = q.rotate(Vec3::new(1.0, 0.0, 0.0));
+
+        assert!(approx(v.x, 0.0) && approx(v.y, 1.0) && approx(v.z, 0.0));
+    }
+
+    #[test]
+    fn test_multiplication_composes_rotations() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Quaternion::from_axis_angle(axis, 0.4);
+        let b = Quaternion::from_axis_angle(axis, 0.6);
+
+        assert!(approx_q(a * b, Quaternion::from_axis_angle(axis, 1.0)));
+        assert!(approx_q(a * a.conjugate(), Quaternion::identity()));
+    }
+
+    #[test]
+    fn test_slerp_endpoints() {
+        let a = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.3);
+        let b = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 1.0), PI * 0.8);
+
+        assert!(approx_q(a.slerp(b, 0.0), a));
+        assert!(approx_q(a.slerp(b, 1.0), b));
+        assert!(approx(a.slerp(b, 0.5).norm(), 1.0));
+    }
+}
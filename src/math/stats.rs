@@ -0,0 +1,123 @@
+//! Streaming statistics over rows of data.
+
+use crate::math::matrix::matrix::Matrix;
+
+/// Per-column running mean and variance, updated one row at a time with
+/// Welford's algorithm so no rows need to be kept around.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningMatrixStats {
+    count: usize,
+    mean: Vec<f64>,
+    // sum of squared deviations from the current mean, per column
+    m2: Vec<f64>,
+}
+
+impl RunningMatrixStats {
+    pub fn new(cols: usize) -> Self {
+        Self {
+            count: 0,
+            mean: vec![0.0; cols],
+            m2: vec![0.0; cols],
+        }
+    }
+
+    pub fn push_row(&mut self, row: &[f64]) {
+        assert_eq!(
+            row.len(),
+            self.mean.len(),
+            "Row length must match column count"
+        );
+
+        self.count += 1;
+        let n = self.count as f64;
+        for ((x, mean), m2) in row.iter().zip(&mut self.mean).zip(&mut self.m2) {
+            let delta = x - *mean;
+            *mean += delta / n;
+            *m2 += delta * (x - *mean);
+        }
+    }
+
+    /// Feeds every row of `data`.
+    pub fn push_matrix(&mut self, data: &Matrix) {
+        for r in 0..data.rows {
+            self.push_row(&data.data[r * data.cols..(r + 1) * data.cols]);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn cols(&self) -> usize {
+        self.mean.len()
+    }
+
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Population variance (divides by `n`); zero before any rows are pushed.
+    pub fn variance(&self) -> Vec<f64> {
+        let n = self.count.max(1) as f64;
+        self.m2.iter().map(|m2| m2 / n).collect()
+    }
+
+    /// Sample variance (divides by `n - 1`); zero with fewer than two rows.
+    pub fn sample_variance(&self) -> Vec<f64> {
+        if self.count < 2 {
+            return vec![0.0; self.m2.len()];
+        }
+        let n = (self.count - 1) as f64;
+        self.m2.iter().map(|m2| m2 / n).collect()
+    }
+
+    pub fn std_dev(&self) -> Vec<f64> {
+        self.variance().into_iter().map(f64::sqrt).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Matrix {
+        let rows = 200;
+        let data = (0..rows)
+            .flat_map(|i| {
+                let t = i as f64;
+                [t.sin() * 3.0 + 1e6, t * 0.25 - 10.0, 7.0]
+            })
+            .collect();
+        Matrix::new(rows, 3, data)
+    }
+
+    #[test]
+    fn test_matches_batch_statistics() {
+        let data = dataset();
+        let mut stats = RunningMatrixStats::new(3);
+        stats.push_matrix(&data);
+
+        assert_eq!(stats.count(), data.rows);
+        for c in 0..3 {
+            let column: Vec<f64> = (0..data.rows).map(|r| data.get(r, c)).collect();
+            let n = column.len() as f64;
+            let mean = column.iter().sum::<f64>() / n;
+            let ss = column.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+
+            assert!((stats.mean()[c] - mean).abs() < 1e-9 * mean.abs().max(1.0));
+            assert!((stats.variance()[c] - ss / n).abs() < 1e-8);
+            assert!((stats.sample_variance()[c] - ss / (n - 1.0)).abs() < 1e-8);
+        }
+        assert_eq!(stats.variance()[2], 0.0);
+    }
+
+    #[test]
+    fn test_empty_and_single_row() {
+        let mut stats = RunningMatrixStats::new(2);
+        assert_eq!(stats.variance(), vec![0.0, 0.0]);
+
+        stats.push_row(&[3.0, -1.0]);
+        assert_eq!(stats.mean(), &[3.0, -1.0]);
+        assert_eq!(stats.sample_variance(), vec![0.0, 0.0]);
+    }
+}
@@ -0,0 +1,164 @@
+//! 4×4 homogeneous transforms for column vectors, OpenGL conventions
+//! (right-handed, camera looking down -z).
+
+use crate::math::geometry::Vec3;
+use crate::math::matrix::matrix::Matrix;
+
+pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
+    let mut m = Matrix::identity(4);
+    m.set(0, 3, x);
+    m.set(1, 3, y);
+    m.set(2, 3, z);
+    m
+}
+
+pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
+    let mut m = Matrix::identity(4);
+    m.set(0, 0, x);
+    m.set(1, 1, y);
+    m.set(2, 2, z);
+    m
+}
+
+pub fn rotation_x(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    let mut m = Matrix::identity(4);
+    m.set(1, 1, c);
+    m.set(1, 2, -s);
+    m.set(2, 1, s);
+    m.set(2, 2, c);
+    m
+}
+
+pub fn rotation_y(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    let mut m = Matrix::identity(4);
+    m.set(0, 0, c);
+    m.set(0, 2, s);
+    m.set(2, 0, -s);
+    m.set(2, 2, c);
+    m
+}
+
+pub fn rotation_z(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    let mut m = Matrix::identity(4);
+    m.set(0, 0, c);
+    m.set(0, 1, -s);
+    m.set(1, 0, s);
+    m.set(1, 1, c);
+    m
+}
+
+/// Perspective projection with vertical field of view `fovy` (radians).
+pub fn perspective(fovy: f64, aspect: f64, near: f64, far: f64) -> Matrix {
+    let f = 1.0 / (fovy / 2.0).tan();
+    let mut m = Matrix::zeros(4, 4);
+    m.set(0, 0, f / aspect);
+    m.set(1, 1, f);
+    m.set(2, 2, (far + near) / (near - far));
+    m.set(2, 3, 2.0 * far * near / (near - far));
+    m.set(3, 2, -1.0);
+    m
+}
+
+/// View matrix placing the camera at `eye` looking towards `target`.
+pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Matrix {
+    let f = (target - eye).normalize();
+    let s = f.cross(up).normalize();
+    let u = s.cross(f);
+
+    let mut m = Matrix::identity(4);
+    for (i, axis) in [s, u, -f].into_iter().enumerate() {
+        m.set(i, 0, axis.x);
+        m.set(i, 1, axis.y);
+        m.set(i, 2, axis.z);
+        m.set(i, 3, -axis.dot(eye));
+    }
+    m
+}
+
+/// Applies `m` to `p` as a point (w = 1), dividing through by the resulting w.
+pub fn transform_point(m: &Matrix, p: Vec3) -> Vec3 {
+    assert!(m.rows == 4 && m.cols == 4, "Transform must be a 4x4 matrix");
+
+    let row = |i: usize| m.get(i, 0) * p.x + m.get(i, 1) * p.y + m.get(i, 2) * p.z + m.get(i, 3);
+    let w = row(3);
+    Vec3::new(row(0) / w, row(1) / w, row(2) / w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::matrix::ops;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_translation_inverse_is_identity() {
+        let t = translation(1.5, -2.0, 3.25);
+        let back = translation(-1.5, 2.0, -3.25);
+        assert!(ops::mul(&t, &back)
+            .unwrap()
+            .approx_eq(&Matrix::identity(4), 1e-9));
+
+        let inv = ops::inv(&t).unwrap();
+        assert!(ops::mul(&t, &inv)
+            .unwrap()
+            .approx_eq(&Matrix::identity(4), 1e-9));
+    }
+
+    #[test]
+    fn test_transform_point() {
+        let p = Vec3::new(1.0, 0.0, 0.0);
+
+        let moved = transform_point(&translation(1.0, 2.0, 3.0), p);
+        assert_eq!(moved, Vec3::new(2.0, 2.0, 3.0));
+
+        let scaled = transform_point(&scaling(2.0, 3.0, 4.0), Vec3::new(1.0, 1.0, 1.0));
+        assert_eq!(scaled, Vec3::new(2.0, 3.0, 4.0));
+
+        let r = transform_point(&rotation_z(FRAC_PI_2), p);
+        assert!(approx(r.x, 0.0) && approx(r.y, 1.0) && approx(r.z, 0.0));
+
+        let r = transform_point(&rotation_x(FRAC_PI_2), Vec3::new(0.0, 1.0, 0.0));
+        assert!(approx(r.x, 0.0) && approx(r.y, 0.0) && approx(r.z, 1.0));
+
+        let r = transform_point(&rotation_y(FRAC_PI_2), Vec3::new(0.0, 0.0, 1.0));
+        assert!(approx(r.x, 1.0) && approx(r.y, 0.0) && approx(r.z, 0.0));
+    }
+
+    #[test]
+    fn test_look_at_orthonormal() {
+        let eye = Vec3::new(3.0, 4.0, 5.0);
+        let view = look_at(eye, Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+
+        let axis = |i: usize| Vec3::new(view.get(i, 0), view.get(i, 1), view.get(i, 2));
+        for i in 0..3 {
+            assert!(approx(axis(i).length(), 1.0));
+            for j in (i + 1)..3 {
+                assert!(approx(axis(i).dot(axis(j)), 0.0));
+            }
+        }
+
+        let origin = transform_point(&view, eye);
+        assert!(approx(origin.length(), 0.0));
+    }
+
+    #[test]
+    fn test_perspective_maps_near_and_far() {
+        let proj = perspective(FRAC_PI_2, 1.0, 1.0, 10.0);
+
+        assert!(approx(
+            transform_point(&proj, Vec3::new(0.0, 0.0, -1.0)).z,
+            -1.0
+        ));
+        assert!(approx(
+            transform_point(&proj, Vec3::new(0.0, 0.0, -10.0)).z,
+            1.0
+        ));
+    }
+}
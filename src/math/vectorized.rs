@@ -0,0 +1,199 @@
+//! Elementwise math over `f64` slices, using AVX when the CPU supports it.
+
+pub fn add_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+    assert_eq!(a.len(), b.len(), "Slice lengths must match");
+    assert_eq!(a.len(), out.len(), "Output length must match inputs");
+
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        unsafe { avx::add(a, b, out) };
+        return;
+    }
+
+    for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+        *o = x + y;
+    }
+}
+
+pub fn sub_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+    assert_eq!(a.len(), b.len(), "Slice lengths must match");
+    assert_eq!(a.len(), out.len(), "Output length must match inputs");
+
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        unsafe { avx::sub(a, b, out) };
+        return;
+    }
+
+    for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+        *o = x - y;
+    }
+}
+
+pub fn mul_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+    assert_eq!(a.len(), b.len(), "Slice lengths must match");
+    assert_eq!(a.len(), out.len(), "Output length must match inputs");
+
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        unsafe { avx::mul(a, b, out) };
+        return;
+    }
+
+    for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+        *o = x * y;
+    }
+}
+
+pub fn scale_slice(a: &[f64], scalar: f64, out: &mut [f64]) {
+    assert_eq!(a.len(), out.len(), "Output length must match input");
+
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        unsafe { avx::scale(a, scalar, out) };
+        return;
+    }
+
+    for (o, x) in out.iter_mut().zip(a) {
+        *o = x * scalar;
+    }
+}
+
+/// Adds `scalar` to every element.
+pub fn offset_slice(a: &[f64], scalar: f64, out: &mut [f64]) {
+    assert_eq!(a.len(), out.len(), "Output length must match input");
+
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        unsafe { avx::offset(a, scalar, out) };
+        return;
+    }
+
+    for (o, x) in out.iter_mut().zip(a) {
+        *o = x + scalar;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx {
+    use std::arch::x86_64::*;
+
+    const LANES: usize = 4;
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn add(a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = a.len() - a.len() % LANES;
+        for i in (0..n).step_by(LANES) {
+            let v = _mm256_add_pd(
+                _mm256_loadu_pd(a.as_ptr().add(i)),
+                _mm256_loadu_pd(b.as_ptr().add(i)),
+            );
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), v);
+        }
+        for i in n..a.len() {
+            out[i] = a[i] + b[i];
+        }
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn sub(a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = a.len() - a.len() % LANES;
+        for i in (0..n).step_by(LANES) {
+            let v = _mm256_sub_pd(
+                _mm256_loadu_pd(a.as_ptr().add(i)),
+                _mm256_loadu_pd(b.as_ptr().add(i)),
+            );
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), v);
+        }
+        for i in n..a.len() {
+            out[i] = a[i] - b[i];
+        }
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn mul(a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = a.len() - a.len() % LANES;
+        for i in (0..n).step_by(LANES) {
+            let v = _mm256_mul_pd(
+                _mm256_loadu_pd(a.as_ptr().add(i)),
+                _mm256_loadu_pd(b.as_ptr().add(i)),
+            );
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), v);
+        }
+        for i in n..a.len() {
+            out[i] = a[i] * b[i];
+        }
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn scale(a: &[f64], scalar: f64, out: &mut [f64]) {
+        let s = _mm256_set1_pd(scalar);
+        let n = a.len() - a.len() % LANES;
+        for i in (0..n).step_by(LANES) {
+            let v = _mm256_mul_pd(_mm256_loadu_pd(a.as_ptr().add(i)), s);
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), v);
+        }
+        for i in n..a.len() {
+            out[i] = a[i] * scalar;
+        }
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn offset(a: &[f64], scalar: f64, out: &mut [f64]) {
+        let s = _mm256_set1_pd(scalar);
+        let n = a.len() - a.len() % LANES;
+        for i in (0..n).step_by(LANES) {
+            let v = _mm256_add_pd(_mm256_loadu_pd(a.as_ptr().add(i)), s);
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), v);
+        }
+        for i in n..a.len() {
+            out[i] = a[i] + scalar;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(len: usize) -> (Vec<f64>, Vec<f64>) {
+        let a = (0..len).map(|i| i as f64 * 0.5 - 3.0).collect();
+        let b = (0..len).map(|i| (i as f64).sqrt() + 1.25).collect();
+        (a, b)
+    }
+
+    #[test]
+    fn test_matches_scalar_for_all_lengths() {
+        for len in [0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 100, 1023] {
+            let (a, b) = inputs(len);
+            let mut out = vec![0.0; len];
+
+            add_slices(&a, &b, &mut out);
+            let want: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x + y).collect();
+            assert_eq!(out, want, "add len {len}");
+
+            sub_slices(&a, &b, &mut out);
+            let want: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+            assert_eq!(out, want, "sub len {len}");
+
+            mul_slices(&a, &b, &mut out);
+            let want: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x * y).collect();
+            assert_eq!(out, want, "mul len {len}");
+
+            scale_slice(&a, -2.5, &mut out);
+            let want: Vec<f64> = a.iter().map(|x| x * -2.5).collect();
+            assert_eq!(out, want, "scale len {len}");
+
+            offset_slice(&a, 0.75, &mut out);
+            let want: Vec<f64> = a.iter().map(|x| x + 0.75).collect();
+            assert_eq!(out, want, "offset len {len}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Slice lengths must match")]
+    fn test_length_mismatch() {
+        let mut out = vec![0.0; 3];
+        add_slices(&[1.0, 2.0, 3.0], &[1.0, 2.0], &mut out);
+    }
+}
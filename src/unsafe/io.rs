@@ -1,15 +1,55 @@
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
 use std::ptr;
 use std::slice;
 
+pub const FADV_NORMAL: i32 = libc::POSIX_FADV_NORMAL;
+pub const FADV_SEQUENTIAL: i32 = libc::POSIX_FADV_SEQUENTIAL;
+pub const FADV_RANDOM: i32 = libc::POSIX_FADV_RANDOM;
+pub const FADV_WILLNEED: i32 = libc::POSIX_FADV_WILLNEED;
+pub const FADV_DONTNEED: i32 = libc::POSIX_FADV_DONTNEED;
+pub const FADV_NOREUSE: i32 = libc::POSIX_FADV_NOREUSE;
+
+// turns a C-style negative return into the current errno
+fn cvt<T: PartialOrd + Default>(ret: T) -> io::Result<T> {
+    if ret < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+// like `cvt`, but reissues calls interrupted by a signal
+fn cvt_retry<T: PartialOrd + Default>(mut f: impl FnMut() -> T) -> io::Result<T> {
+    loop {
+        match cvt(f()) {
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            ret => return ret,
+        }
+    }
+}
+
 pub struct RawIO {
     fd: RawFd,
     owned: bool,
 }
 
+/// The subset of `fstat` results needed to size buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub size: u64,
+    /// Preferred I/O block size.
+    pub blksize: u64,
+    /// Number of 512-byte blocks allocated.
+    pub blocks: u64,
+    /// File type and permission bits, as in `st_mode`.
+    pub mode: u32,
+}
+
 impl RawIO {
     pub unsafe fn from_raw_fd(fd: RawFd, owned: bool) -> Self {
         Self { fd, owned }
@@ -25,42 +65,132 @@ impl RawIO {
         self.fd
     }
 
-    pub unsafe fn read_direct(&self, buf: *mut u8, len: usize) -> io::Result<usize> {
-        let ret = libc::read(self.fd, buf as *mut libc::c_void, len);
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as usize)
+    /// Gives up ownership of the descriptor: it is returned instead of being
+    /// closed when `self` is dropped.
+    pub fn into_raw_fd(mut self) -> RawFd {
+        self.owned = false;
+        self.fd
+    }
+
+    /// Duplicates the descriptor. The copy shares the file offset and status
+    /// flags with `self` but owns its own fd, closed when it is dropped. Like
+    /// std's `try_clone`, the copy is close-on-exec.
+    pub fn dup(&self) -> io::Result<RawIO> {
+        let fd = cvt(unsafe { libc::fcntl(self.fd, libc::F_DUPFD_CLOEXEC, 0) })?;
+        Ok(Self { fd, owned: true })
+    }
+
+    /// Duplicates the descriptor onto `newfd`, closing whatever `newfd` was
+    /// open as first. The returned `RawIO` owns `newfd`, so dropping it closes
+    /// `newfd` (for example stdout, after redirecting it). Fails with
+    /// `InvalidInput` if `newfd` is this descriptor, which would leave two
+    /// owners of one fd.
+    ///
+    /// # Safety
+    /// The caller must own `newfd`: nothing else may use or close it
+    /// afterwards, since it is closed here and again when the result drops.
+    pub unsafe fn dup2(&self, newfd: RawFd) -> io::Result<RawIO> {
+        if newfd == self.fd {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "dup2 onto the same descriptor",
+            ));
         }
+        let fd = cvt_retry(|| libc::dup2(self.fd, newfd))?;
+        Ok(Self { fd, owned: true })
+    }
+
+    pub unsafe fn read_direct(&self, buf: *mut u8, len: usize) -> io::Result<usize> {
+        cvt_retry(|| libc::read(self.fd, buf as *mut libc::c_void, len)).map(|ret| ret as usize)
     }
 
     pub unsafe fn write_direct(&self, buf: *const u8, len: usize) -> io::Result<usize> {
-        let ret = libc::write(self.fd, buf as *const libc::c_void, len);
-        if ret < 0 {
-            Err(io::Error::last_os_error())
+        cvt_retry(|| libc::write(self.fd, buf as *const libc::c_void, len)).map(|ret| ret as usize)
+    }
+
+    pub fn stat(&self) -> io::Result<FileMetadata> {
+        let mut st: libc::stat = unsafe { mem::zeroed() };
+        cvt(unsafe { libc::fstat(self.fd, &mut st) })?;
+
+        Ok(FileMetadata {
+            size: st.st_size as u64,
+            blksize: st.st_blksize as u64,
+            blocks: st.st_blocks as u64,
+            mode: st.st_mode,
+        })
+    }
+
+    /// Toggles `O_NONBLOCK`. Once set, reads and writes that would block fail
+    /// with `io::ErrorKind::WouldBlock` instead.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        let flags = cvt(unsafe { libc::fcntl(self.fd, libc::F_GETFL) })?;
+        let new_flags = if nonblocking {
+            flags | libc::O_NONBLOCK
         } else {
-            Ok(ret as usize)
+            flags & !libc::O_NONBLOCK
+        };
+
+        if new_flags != flags {
+            cvt(unsafe { libc::fcntl(self.fd, libc::F_SETFL, new_flags) })?;
         }
+        Ok(())
     }
 
     pub fn seek(&self, pos: i64, whence: i32) -> io::Result<u64> {
-        let ret = unsafe { libc::lseek(self.fd, pos, whence) };
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as u64)
-        }
+        cvt(unsafe { libc::lseek(self.fd, pos, whence) }).map(|ret| ret as u64)
     }
 
     pub fn fsync(&self) -> io::Result<()> {
-        let ret = unsafe { libc::fsync(self.fd) };
-        if ret < 0 {
-            Err(io::Error::last_os_error())
+        cvt_retry(|| unsafe { libc::fsync(self.fd) })?;
+        Ok(())
+    }
+
+    /// Hints the kernel about the expected access pattern for
+    /// `[offset, offset + len)`; a `len` of 0 means to the end of the file.
+    pub fn fadvise(&self, offset: i64, len: i64, advice: i32) -> io::Result<()> {
+        let ret = unsafe { libc::posix_fadvise(self.fd, offset, len, advice) };
+        if ret != 0 {
+            Err(io::Error::from_raw_os_error(ret))
         } else {
             Ok(())
         }
     }
 
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_EX)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_SH)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_UN)
+    }
+
+    /// Returns `Ok(false)` instead of blocking when another open file
+    /// description already holds a conflicting lock.
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.try_flock(libc::LOCK_EX)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.try_flock(libc::LOCK_SH)
+    }
+
+    fn flock(&self, operation: i32) -> io::Result<()> {
+        cvt_retry(|| unsafe { libc::flock(self.fd, operation) })?;
+        Ok(())
+    }
+
+    fn try_flock(&self, operation: i32) -> io::Result<bool> {
+        match self.flock(operation | libc::LOCK_NB) {
+            Ok(()) => Ok(true),
+            Err(err) if err.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     pub unsafe fn mmap(
         &self,
         len: usize,
@@ -78,75 +208,63 @@ impl RawIO {
     }
 
     pub unsafe fn munmap(&self, addr: *mut u8, len: usize) -> io::Result<()> {
-        let ret = libc::munmap(addr as *mut libc::c_void, len);
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(())
-        }
+        cvt(libc::munmap(addr as *mut libc::c_void, len))?;
+        Ok(())
     }
 
     pub unsafe fn madvise(&self, addr: *mut u8, len: usize, advice: i32) -> io::Result<()> {
-        let ret = libc::madvise(addr as *mut libc::c_void, len, advice);
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(())
-        }
+        cvt(libc::madvise(addr as *mut libc::c_void, len, advice))?;
+        Ok(())
     }
 
     pub fn pread(&self, buf: &mut [u8], offset: i64) -> io::Result<usize> {
-        let ret = unsafe {
+        cvt_retry(|| unsafe {
             libc::pread(
                 self.fd,
                 buf.as_mut_ptr() as *mut libc::c_void,
                 buf.len(),
                 offset,
             )
-        };
-
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as usize)
-        }
+        })
+        .map(|ret| ret as usize)
     }
 
     pub fn pwrite(&self, buf: &[u8], offset: i64) -> io::Result<usize> {
-        let ret = unsafe {
+        cvt_retry(|| unsafe {
             libc::pwrite(
                 self.fd,
                 buf.as_ptr() as *const libc::c_void,
                 buf.len(),
                 offset,
             )
-        };
-
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as usize)
-        }
+        })
+        .map(|ret| ret as usize)
     }
 
     pub fn readv(&self, iovecs: &mut [libc::iovec]) -> io::Result<usize> {
-        let ret = unsafe { libc::readv(self.fd, iovecs.as_mut_ptr(), iovecs.len() as i32) };
-
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as usize)
-        }
+        cvt_retry(|| unsafe { libc::readv(self.fd, iovecs.as_mut_ptr(), iovecs.len() as i32) })
+            .map(|ret| ret as usize)
     }
 
     pub fn writev(&self, iovecs: &[libc::iovec]) -> io::Result<usize> {
-        let ret = unsafe { libc::writev(self.fd, iovecs.as_ptr(), iovecs.len() as i32) };
+        cvt_retry(|| unsafe { libc::writev(self.fd, iovecs.as_ptr(), iovecs.len() as i32) })
+            .map(|ret| ret as usize)
+    }
 
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(ret as usize)
-        }
+    /// `readv` at `offset`, leaving the file position untouched.
+    pub fn preadv(&self, iovecs: &mut [libc::iovec], offset: i64) -> io::Result<usize> {
+        cvt_retry(|| unsafe {
+            libc::preadv(self.fd, iovecs.as_mut_ptr(), iovecs.len() as i32, offset)
+        })
+        .map(|ret| ret as usize)
+    }
+
+    /// `writev` at `offset`, leaving the file position untouched.
+    pub fn pwritev(&self, iovecs: &[libc::iovec], offset: i64) -> io::Result<usize> {
+        cvt_retry(|| unsafe {
+            libc::pwritev(self.fd, iovecs.as_ptr(), iovecs.len() as i32, offset)
+        })
+        .map(|ret| ret as usize)
     }
 
     pub unsafe fn read_vectored_direct(&self, bufs: &mut [&mut [u8]]) -> io::Result<usize> {
@@ -176,21 +294,13 @@ impl RawIO {
     }
 
     pub fn allocate(&self, offset: i64, len: i64) -> io::Result<()> {
-        let ret = unsafe { libc::fallocate(self.fd, 0, offset, len) };
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(())
-        }
+        cvt_retry(|| unsafe { libc::fallocate(self.fd, 0, offset, len) })?;
+        Ok(())
     }
 
     pub fn truncate(&self, len: i64) -> io::Result<()> {
-        let ret = unsafe { libc::ftruncate(self.fd, len) };
-        if ret < 0 {
-            Err(io::Error::last_os_error())
-        } else {
-            Ok(())
-        }
+        cvt_retry(|| unsafe { libc::ftruncate(self.fd, len) })?;
+        Ok(())
     }
 }
 
@@ -205,6 +315,10 @@ impl Drop for RawIO {
 pub struct MemoryMappedFile {
     addr: *mut u8,
     len: usize,
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    prot: i32,
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    flags: i32,
     io: Option<RawIO>,
 }
 
@@ -220,10 +334,34 @@ impl MemoryMappedFile {
         Ok(Self {
             addr,
             len,
+            prot,
+            flags,
             io: Some(io),
         })
     }
 
+    /// Opens `path` and maps the whole file, read-only unless `write` is set.
+    ///
+    /// # Safety
+    /// Same contract as `new`: the file must not be truncated, or changed
+    /// through other handles while the mapping is in use. Empty files fail
+    /// with `InvalidInput`, since a zero-length mapping is not allowed.
+    pub unsafe fn from_path(path: impl AsRef<Path>, write: bool) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(write).open(path)?;
+        let size = RawIO::from_raw_fd(file.as_raw_fd(), false).stat()?.size;
+
+        if size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot map an empty file",
+            ));
+        }
+        let len = usize::try_from(size)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file too large to map"))?;
+
+        Self::new(file, len, write)
+    }
+
     pub unsafe fn anonymous(len: usize) -> io::Result<Self> {
         let prot = libc::PROT_READ | libc::PROT_WRITE;
         let flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS;
@@ -236,16 +374,110 @@ impl MemoryMappedFile {
             Ok(Self {
                 addr: addr as *mut u8,
                 len,
+                prot,
+                flags,
                 io: None,
             })
         }
     }
 
+    /// Anonymous mapping backed by 2MB huge pages, with `len` rounded up to a
+    /// whole number of huge pages.
+    ///
+    /// # Safety
+    /// Same contract as `anonymous`. Fails if the kernel has no huge pages
+    /// reserved (see `/proc/sys/vm/nr_hugepages`); callers should fall back
+    /// to `anonymous` in that case.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn anonymous_hugepage(len: usize) -> io::Result<Self> {
+        const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+        const MAP_HUGE_2MB: i32 = 21 << 26;
+
+        let len = len
+            .checked_next_multiple_of(HUGE_PAGE_SIZE)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "length overflow"))?;
+        let prot = libc::PROT_READ | libc::PROT_WRITE;
+        let flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_HUGETLB | MAP_HUGE_2MB;
+
+        let addr = libc::mmap(ptr::null_mut(), len, prot, flags, -1, 0);
+
+        if addr == libc::MAP_FAILED {
+            let err = io::Error::last_os_error();
+            Err(io::Error::new(
+                err.kind(),
+                format!("huge page mapping of {} bytes rejected: {}", len, err),
+            ))
+        } else {
+            Ok(Self {
+                addr: addr as *mut u8,
+                len,
+                prot,
+                flags,
+                io: None,
+            })
+        }
+    }
+
+    /// Changes the mapped length to `new_len`, possibly moving the mapping.
+    ///
+    /// # Safety
+    /// Any pointers or slices previously obtained from this mapping are
+    /// invalidated. For file-backed mappings, pages past the end of the file
+    /// must not be accessed.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn remap(&mut self, new_len: usize) -> io::Result<()> {
+        let addr = libc::mremap(
+            self.addr as *mut libc::c_void,
+            self.len,
+            new_len,
+            libc::MREMAP_MAYMOVE,
+        );
+
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        self.addr = addr as *mut u8;
+        self.len = new_len;
+        Ok(())
+    }
+
+    /// Changes the mapped length to `new_len`, possibly moving the mapping.
+    ///
+    /// # Safety
+    /// Any pointers or slices previously obtained from this mapping are
+    /// invalidated. For file-backed mappings, pages past the end of the file
+    /// must not be accessed.
+    #[cfg(not(target_os = "linux"))]
+    pub unsafe fn remap(&mut self, new_len: usize) -> io::Result<()> {
+        let (fd, offset) = match self.io {
+            Some(ref io) => (io.raw_fd(), 0),
+            None => (-1, 0),
+        };
+
+        let addr = libc::mmap(ptr::null_mut(), new_len, self.prot, self.flags, fd, offset);
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        if self.io.is_none() {
+            ptr::copy_nonoverlapping(self.addr, addr as *mut u8, self.len.min(new_len));
+        }
+
+        libc::munmap(self.addr as *mut libc::c_void, self.len);
+        self.addr = addr as *mut u8;
+        self.len = new_len;
+        Ok(())
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         unsafe { slice::from_raw_parts(self.addr, self.len) }
     }
 
+    /// Panics if the mapping was not created with `PROT_WRITE`, since writing
+    /// through the slice would fault.
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        assert!(self.prot & libc::PROT_WRITE != 0, "Mapping is not writable");
         unsafe { slice::from_raw_parts_mut(self.addr, self.len) }
     }
 
@@ -257,29 +489,91 @@ impl MemoryMappedFile {
         self.addr
     }
 
+    /// Reads a value of type T at `offset`, or `None` if it would run past
+    /// the end of the mapping.
+    pub fn read_at<T: Copy>(&self, offset: usize) -> Option<T> {
+        let end = offset.checked_add(mem::size_of::<T>())?;
+        if end > self.len {
+            return None;
+        }
+        Some(unsafe { ptr::read_unaligned(self.addr.add(offset) as *const T) })
+    }
+
+    /// Writes a value of type T at `offset`.
+    ///
+    /// # Panics
+    /// Panics if the write would go out of bounds.
+    pub fn write_at<T>(&mut self, offset: usize, value: T) {
+        assert!(
+            offset
+                .checked_add(mem::size_of::<T>())
+                .is_some_and(|end| end <= self.len),
+            "Write out of bounds"
+        );
+        unsafe { ptr::write_unaligned(self.addr.add(offset) as *mut T, value) };
+    }
+
     pub fn advise(&self, advice: i32) -> io::Result<()> {
         if let Some(ref io) = self.io {
             unsafe { io.madvise(self.addr, self.len, advice) }
         } else {
             unsafe {
-                let ret = libc::madvise(self.addr as *mut libc::c_void, self.len, advice);
-                if ret < 0 {
-                    Err(io::Error::last_os_error())
-                } else {
-                    Ok(())
-                }
+                cvt(libc::madvise(
+                    self.addr as *mut libc::c_void,
+                    self.len,
+                    advice,
+                ))?;
+                Ok(())
             }
         }
     }
 
+    /// Pins the mapped pages in RAM so they are never swapped out.
+    ///
+    /// Fails with `ENOMEM` when the mapping exceeds `RLIMIT_MEMLOCK`, or
+    /// `EPERM` when the process is not allowed to lock memory at all.
+    pub fn lock(&self) -> io::Result<()> {
+        cvt(unsafe { libc::mlock(self.addr as *const libc::c_void, self.len) })?;
+        Ok(())
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { libc::munlock(self.addr as *const libc::c_void, self.len) })?;
+        Ok(())
+    }
+
     pub fn sync(&self, sync_flags: i32) -> io::Result<()> {
         unsafe {
-            let ret = libc::msync(self.addr as *mut libc::c_void, self.len, sync_flags);
-            if ret < 0 {
-                Err(io::Error::last_os_error())
-            } else {
-                Ok(())
-            }
+            cvt(libc::msync(
+                self.addr as *mut libc::c_void,
+                self.len,
+                sync_flags,
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Flushes only `[offset, offset + len)` instead of the whole mapping.
+    /// The range is widened outward to page boundaries.
+    pub fn sync_range(&self, offset: usize, len: usize, sync_flags: i32) -> io::Result<()> {
+        let end = offset
+            .checked_add(len)
+            .filter(|&end| end <= self.len)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "sync range out of bounds")
+            })?;
+
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let start = offset & !(page - 1);
+        let end = end.next_multiple_of(page);
+
+        unsafe {
+            cvt(libc::msync(
+                self.addr.add(start) as *mut libc::c_void,
+                end - start,
+                sync_flags,
+            ))?;
+            Ok(())
         }
     }
 
@@ -296,7 +590,45 @@ impl Drop for MemoryMappedFile {
     }
 }
 
+impl Deref for MemoryMappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Panics on a read-only mapping, like `as_mut_slice`.
+impl DerefMut for MemoryMappedFile {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl AsRef<[u8]> for MemoryMappedFile {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Panics on a read-only mapping, like `as_mut_slice`.
+impl AsMut<[u8]> for MemoryMappedFile {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+/// Copies from `src` to `dst` until end of input through a userspace buffer.
+/// Short writes are continued and, like every `RawIO` read and write, calls
+/// interrupted by a signal (`EINTR`) are retried, so pipes and sockets work as
+/// well as files.
+///
+/// A `buffer_size` of 0 uses the source's preferred block size from `stat`.
 pub fn direct_copy(src: &RawIO, dst: &RawIO, buffer_size: usize) -> io::Result<u64> {
+    let buffer_size = match buffer_size {
+        0 => (src.stat()?.blksize as usize).max(512),
+        n => n,
+    };
     let mut buffer = Vec::with_capacity(buffer_size);
     unsafe {
         buffer.set_len(buffer_size);
@@ -328,35 +660,1172 @@ pub fn direct_copy(src: &RawIO, dst: &RawIO, buffer_size: usize) -> io::Result<u
     Ok(total_copied)
 }
 
+#[cfg(target_os = "linux")]
+const MAX_SPLICE: usize = 0x7ffff000;
+
+/// Copies up to `len` bytes from `src` to `dst` in the kernel, stopping early
+/// at end of input.
+///
+/// `splice` needs a pipe on one side. When neither fd is one, the kernel
+/// answers `EINVAL` and the copy goes through an internal pipe pair instead,
+/// so file-to-file copies work too.
 #[cfg(target_os = "linux")]
 pub fn splice_copy(src: &RawIO, dst: &RawIO, len: usize) -> io::Result<u64> {
     let mut total = 0;
     let mut remaining = len;
 
     while remaining > 0 {
-        let chunk_size = remaining.min(0x7ffff000);
-        let ret = unsafe {
-            libc::splice(
-                src.raw_fd(),
-                ptr::null_mut(),
-                dst.raw_fd(),
-                ptr::null_mut(),
-                chunk_size,
-                libc::SPLICE_F_MOVE,
-            )
+        let ret = match splice_once(src.raw_fd(), dst.raw_fd(), remaining.min(MAX_SPLICE)) {
+            Err(err) if total == 0 && err.raw_os_error() == Some(libc::EINVAL) => {
+                return splice_through_pipe(src, dst, len);
+            }
+            ret => ret?,
         };
 
-        if ret < 0 {
-            return Err(io::Error::last_os_error());
-        }
-
         if ret == 0 {
             break;
         }
 
         total += ret as u64;
-        remaining -= ret as usize;
+        remaining -= ret;
     }
 
     Ok(total)
 }
+
+#[cfg(target_os = "linux")]
+fn splice_through_pipe(src: &RawIO, dst: &RawIO, len: usize) -> io::Result<u64> {
+    let mut fds = [0; 2];
+    cvt(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) })?;
+    // owned, so both ends are closed on every return path
+    let (pipe_r, pipe_w) = unsafe {
+        (
+            RawIO::from_raw_fd(fds[0], true),
+            RawIO::from_raw_fd(fds[1], true),
+        )
+    };
+
+    let mut total = 0;
+    let mut remaining = len;
+
+    while remaining > 0 {
+        let filled = splice_once(src.raw_fd(), pipe_w.raw_fd(), remaining.min(MAX_SPLICE))?;
+        if filled == 0 {
+            break;
+        }
+
+        // drain the pipe completely so the next fill starts empty
+        let mut drained = 0;
+        while drained < filled {
+            let n = splice_once(pipe_r.raw_fd(), dst.raw_fd(), filled - drained)?;
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write"));
+            }
+            drained += n;
+        }
+
+        total += filled as u64;
+        remaining -= filled;
+    }
+
+    Ok(total)
+}
+
+#[cfg(target_os = "linux")]
+fn splice_once(from: RawFd, to: RawFd, len: usize) -> io::Result<usize> {
+    cvt_retry(|| unsafe {
+        libc::splice(
+            from,
+            ptr::null_mut(),
+            to,
+            ptr::null_mut(),
+            len,
+            libc::SPLICE_F_MOVE,
+        )
+    })
+    .map(|ret| ret as usize)
+}
+
+#[cfg(all(target_os = "linux", feature = "uring"))]
+pub use uring::{pread_batch, Completion, IoUring};
+
+#[cfg(all(target_os = "linux", feature = "uring"))]
+mod uring {
+    use super::{cvt, RawIO};
+    use std::io;
+    use std::os::unix::io::RawFd;
+    use std::ptr;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const IORING_OFF_SQ_RING: i64 = 0;
+    const IORING_OFF_CQ_RING: i64 = 0x8000000;
+    const IORING_OFF_SQES: i64 = 0x10000000;
+
+    const IORING_FEAT_SINGLE_MMAP: u32 = 1;
+    const IORING_ENTER_GETEVENTS: u32 = 1;
+
+    const IORING_OP_READ: u8 = 22;
+    const IORING_OP_WRITE: u8 = 23;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct SqRingOffsets {
+        head: u32,
+        tail: u32,
+        ring_mask: u32,
+        ring_entries: u32,
+        flags: u32,
+        dropped: u32,
+        array: u32,
+        resv1: u32,
+        user_addr: u64,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CqRingOffsets {
+        head: u32,
+        tail: u32,
+        ring_mask: u32,
+        ring_entries: u32,
+        overflow: u32,
+        cqes: u32,
+        flags: u32,
+        resv1: u32,
+        user_addr: u64,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Params {
+        sq_entries: u32,
+        cq_entries: u32,
+        flags: u32,
+        sq_thread_cpu: u32,
+        sq_thread_idle: u32,
+        features: u32,
+        wq_fd: u32,
+        resv: [u32; 3],
+        sq_off: SqRingOffsets,
+        cq_off: CqRingOffsets,
+    }
+
+    #[repr(C)]
+    struct Sqe {
+        opcode: u8,
+        flags: u8,
+        ioprio: u16,
+        fd: i32,
+        off: u64,
+        addr: u64,
+        len: u32,
+        rw_flags: u32,
+        user_data: u64,
+        buf_index: u16,
+        personality: u16,
+        splice_fd_in: i32,
+        addr3: u64,
+        pad: u64,
+    }
+
+    #[repr(C)]
+    struct Cqe {
+        user_data: u64,
+        res: i32,
+        flags: u32,
+    }
+
+    struct Mapping {
+        addr: *mut u8,
+        len: usize,
+    }
+
+    impl Mapping {
+        unsafe fn new(fd: RawFd, len: usize, offset: i64) -> io::Result<Self> {
+            let addr = libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd,
+                offset,
+            );
+
+            if addr == libc::MAP_FAILED {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(Self {
+                    addr: addr as *mut u8,
+                    len,
+                })
+            }
+        }
+
+        unsafe fn at<T>(&self, offset: u32) -> *mut T {
+            self.addr.add(offset as usize) as *mut T
+        }
+    }
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            unsafe { libc::munmap(self.addr as *mut libc::c_void, self.len) };
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Completion {
+        pub user_data: u64,
+        pub result: io::Result<usize>,
+    }
+
+    /// A single `io_uring` instance driven through raw syscalls.
+    ///
+    /// Reads and writes are queued with `push_pread`/`push_pwrite`, handed to
+    /// the kernel in one `io_uring_enter` by `submit`, and collected with
+    /// `pop_completion`.
+    pub struct IoUring {
+        fd: RawFd,
+        _sq_ring: Mapping,
+        _cq_ring: Option<Mapping>,
+        sqes: Mapping,
+        sq_head: *const AtomicU32,
+        sq_tail: *const AtomicU32,
+        sq_mask: u32,
+        sq_entries: u32,
+        sq_array: *mut u32,
+        cq_head: *const AtomicU32,
+        cq_tail: *const AtomicU32,
+        cq_mask: u32,
+        cqes: *const Cqe,
+        local_tail: u32,
+        in_flight: usize,
+    }
+
+    impl IoUring {
+        pub fn new(entries: u32) -> io::Result<Self> {
+            let mut params = Params::default();
+            let fd = cvt(unsafe {
+                libc::syscall(
+                    libc::SYS_io_uring_setup,
+                    entries,
+                    &mut params as *mut Params,
+                )
+            })? as RawFd;
+
+            match unsafe { Self::map_rings(fd, &params) } {
+                Ok(ring) => Ok(ring),
+                Err(err) => {
+                    unsafe { libc::close(fd) };
+                    Err(err)
+                }
+            }
+        }
+
+        unsafe fn map_rings(fd: RawFd, params: &Params) -> io::Result<Self> {
+            let sq_len = params.sq_off.array as usize
+                + params.sq_entries as usize * std::mem::size_of::<u32>();
+            let cq_len = params.cq_off.cqes as usize
+                + params.cq_entries as usize * std::mem::size_of::<Cqe>();
+            let single_mmap = params.features & IORING_FEAT_SINGLE_MMAP != 0;
+
+            let sq_ring = Mapping::new(
+                fd,
+                if single_mmap {
+                    sq_len.max(cq_len)
+                } else {
+                    sq_len
+                },
+                IORING_OFF_SQ_RING,
+            )?;
+            let cq_ring = if single_mmap {
+                None
+            } else {
+                Some(Mapping::new(fd, cq_len, IORING_OFF_CQ_RING)?)
+            };
+            let sqes = Mapping::new(
+                fd,
+                params.sq_entries as usize * std::mem::size_of::<Sqe>(),
+                IORING_OFF_SQES,
+            )?;
+
+            let cq = cq_ring.as_ref().unwrap_or(&sq_ring);
+            let sq_off = &params.sq_off;
+            let cq_off = &params.cq_off;
+
+            let sq_tail = sq_ring.at::<AtomicU32>(sq_off.tail);
+            let local_tail = (*sq_tail).load(Ordering::Acquire);
+
+            Ok(Self {
+                fd,
+                sq_head: sq_ring.at(sq_off.head),
+                sq_tail,
+                sq_mask: *sq_ring.at::<u32>(sq_off.ring_mask),
+                sq_entries: *sq_ring.at::<u32>(sq_off.ring_entries),
+                sq_array: sq_ring.at(sq_off.array),
+                cq_head: cq.at(cq_off.head),
+                cq_tail: cq.at(cq_off.tail),
+                cq_mask: *cq.at::<u32>(cq_off.ring_mask),
+                cqes: cq.at(cq_off.cqes),
+                _sq_ring: sq_ring,
+                _cq_ring: cq_ring,
+                sqes,
+                local_tail,
+                in_flight: 0,
+            })
+        }
+
+        pub fn raw_fd(&self) -> RawFd {
+            self.fd
+        }
+
+        pub fn in_flight(&self) -> usize {
+            self.in_flight
+        }
+
+        /// Queues a positional read of `len` bytes into `buf`.
+        ///
+        /// # Safety
+        /// `buf` must stay valid for writes of `len` bytes until the matching
+        /// completion has been popped.
+        pub unsafe fn push_pread(
+            &mut self,
+            io: &RawIO,
+            buf: *mut u8,
+            len: usize,
+            offset: i64,
+            user_data: u64,
+        ) -> io::Result<()> {
+            self.push(
+                IORING_OP_READ,
+                io.raw_fd(),
+                buf as u64,
+                len,
+                offset,
+                user_data,
+            )
+        }
+
+        /// Queues a positional write of `len` bytes from `buf`.
+        ///
+        /// # Safety
+        /// `buf` must stay valid for reads of `len` bytes until the matching
+        /// completion has been popped.
+        pub unsafe fn push_pwrite(
+            &mut self,
+            io: &RawIO,
+            buf: *const u8,
+            len: usize,
+            offset: i64,
+            user_data: u64,
+        ) -> io::Result<()> {
+            self.push(
+                IORING_OP_WRITE,
+                io.raw_fd(),
+                buf as u64,
+                len,
+                offset,
+                user_data,
+            )
+        }
+
+        unsafe fn push(
+            &mut self,
+            opcode: u8,
+            fd: RawFd,
+            addr: u64,
+            len: usize,
+            offset: i64,
+            user_data: u64,
+        ) -> io::Result<()> {
+            let head = (*self.sq_head).load(Ordering::Acquire);
+            if self.local_tail.wrapping_sub(head) >= self.sq_entries {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "submission queue full",
+                ));
+            }
+
+            let index = self.local_tail & self.sq_mask;
+            let sqe = (self.sqes.addr as *mut Sqe).add(index as usize);
+            ptr::write(
+                sqe,
+                Sqe {
+                    opcode,
+                    flags: 0,
+                    ioprio: 0,
+                    fd,
+                    off: offset as u64,
+                    addr,
+                    len: len as u32,
+                    rw_flags: 0,
+                    user_data,
+                    buf_index: 0,
+                    personality: 0,
+                    splice_fd_in: 0,
+                    addr3: 0,
+                    pad: 0,
+                },
+            );
+            *self.sq_array.add(index as usize) = index;
+
+            self.local_tail = self.local_tail.wrapping_add(1);
+            Ok(())
+        }
+
+        /// Hands every queued request to the kernel and blocks until at least
+        /// `wait_nr` completions are available. Returns the number submitted.
+        pub fn submit_and_wait(&mut self, wait_nr: u32) -> io::Result<usize> {
+            let head = unsafe { (*self.sq_head).load(Ordering::Acquire) };
+            let to_submit = self.local_tail.wrapping_sub(head);
+            unsafe { (*self.sq_tail).store(self.local_tail, Ordering::Release) };
+
+            let flags = if wait_nr > 0 {
+                IORING_ENTER_GETEVENTS
+            } else {
+                0
+            };
+            let ret = unsafe {
+                libc::syscall(
+                    libc::SYS_io_uring_enter,
+                    self.fd,
+                    to_submit,
+                    wait_nr,
+                    flags,
+                    ptr::null::<libc::sigset_t>(),
+                    0usize,
+                )
+            };
+
+            let submitted = cvt(ret)? as usize;
+            self.in_flight += submitted;
+            Ok(submitted)
+        }
+
+        pub fn submit(&mut self) -> io::Result<usize> {
+            self.submit_and_wait(0)
+        }
+
+        pub fn pop_completion(&mut self) -> Option<Completion> {
+            let head_ref = unsafe { &*self.cq_head };
+            let head = head_ref.load(Ordering::Relaxed);
+            let tail = unsafe { (*self.cq_tail).load(Ordering::Acquire) };
+
+            if head == tail {
+                return None;
+            }
+
+            let cqe = unsafe { &*self.cqes.add((head & self.cq_mask) as usize) };
+            let completion = Completion {
+                user_data: cqe.user_data,
+                result: if cqe.res < 0 {
+                    Err(io::Error::from_raw_os_error(-cqe.res))
+                } else {
+                    Ok(cqe.res as usize)
+                },
+            };
+
+            head_ref.store(head.wrapping_add(1), Ordering::Release);
+            self.in_flight = self.in_flight.saturating_sub(1);
+            Some(completion)
+        }
+    }
+
+    impl Drop for IoUring {
+        fn drop(&mut self) {
+            loop {
+                while self.pop_completion().is_some() {}
+
+                let head = unsafe { (*self.sq_head).load(Ordering::Acquire) };
+                if self.in_flight == 0 && head == self.local_tail {
+                    break;
+                }
+                if self.submit_and_wait(1).is_err() {
+                    break;
+                }
+            }
+
+            unsafe { libc::close(self.fd) };
+        }
+    }
+
+    /// Reads every `(buf, offset)` pair from `io`, batching through `io_uring`
+    /// when the kernel supports it and falling back to one `pread` per request
+    /// otherwise. Results are returned in request order.
+    pub fn pread_batch(io: &RawIO, reqs: &mut [(&mut [u8], i64)]) -> Vec<io::Result<usize>> {
+        if reqs.is_empty() {
+            return Vec::new();
+        }
+
+        let batched = IoUring::new(reqs.len().next_power_of_two() as u32)
+            .and_then(|mut ring| uring_batch(&mut ring, io, reqs));
+
+        match batched {
+            Ok(results) => results,
+            Err(_) => reqs
+                .iter_mut()
+                .map(|(buf, off)| io.pread(buf, *off))
+                .collect(),
+        }
+    }
+
+    fn uring_batch(
+        ring: &mut IoUring,
+        io: &RawIO,
+        reqs: &mut [(&mut [u8], i64)],
+    ) -> io::Result<Vec<io::Result<usize>>> {
+        let mut results: Vec<Option<io::Result<usize>>> = reqs.iter().map(|_| None).collect();
+        let mut next = 0;
+        let mut done = 0;
+
+        while done < reqs.len() {
+            while next < reqs.len() {
+                let (buf, off) = &mut reqs[next];
+                let pushed =
+                    unsafe { ring.push_pread(io, buf.as_mut_ptr(), buf.len(), *off, next as u64) };
+                if pushed.is_err() {
+                    break;
+                }
+                next += 1;
+            }
+
+            ring.submit_and_wait(1)?;
+
+            while let Some(completion) = ring.pop_completion() {
+                results[completion.user_data as usize] = Some(completion.result);
+                done += 1;
+            }
+        }
+
+        Ok(results.into_iter().map(Option::unwrap).collect())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::fs::{self, OpenOptions};
+        use std::io::Write;
+
+        #[test]
+        fn test_batched_reads_match_pread() {
+            let path = std::env::temp_dir().join(format!("wg-utils-uring-{}", std::process::id()));
+            let data: Vec<u8> = (0..16384u32).map(|i| (i % 251) as u8).collect();
+            fs::write(&path, &data).unwrap();
+
+            let file = OpenOptions::new().read(true).open(&path).unwrap();
+            let io = unsafe { RawIO::from_file(file) };
+
+            let offsets = [0i64, 100, 4096, 8000, 12288, 16000];
+            let mut expected: Vec<Vec<u8>> = offsets.iter().map(|_| vec![0u8; 512]).collect();
+            for (buf, &off) in expected.iter_mut().zip(&offsets) {
+                let n = io.pread(buf, off).unwrap();
+                buf.truncate(n);
+            }
+
+            let mut bufs: Vec<Vec<u8>> = offsets.iter().map(|_| vec![0u8; 512]).collect();
+            let mut reqs: Vec<(&mut [u8], i64)> = bufs
+                .iter_mut()
+                .zip(&offsets)
+                .map(|(buf, &off)| (buf.as_mut_slice(), off))
+                .collect();
+            let results = pread_batch(&io, &mut reqs);
+
+            for ((result, buf), want) in results.into_iter().zip(&bufs).zip(&expected) {
+                let n = result.unwrap();
+                assert_eq!(&buf[..n], &want[..]);
+            }
+
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn test_ring_completions() {
+            let mut ring = match IoUring::new(8) {
+                Ok(ring) => ring,
+                Err(err) => {
+                    eprintln!("skipping: io_uring unavailable ({err})");
+                    return;
+                }
+            };
+
+            let path = std::env::temp_dir().join(format!("wg-utils-ring-{}", std::process::id()));
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(b"hello io_uring").unwrap();
+            let io = unsafe { RawIO::from_file(file) };
+
+            let mut a = [0u8; 5];
+            let mut b = [0u8; 8];
+            unsafe {
+                ring.push_pread(&io, a.as_mut_ptr(), a.len(), 0, 1).unwrap();
+                ring.push_pread(&io, b.as_mut_ptr(), b.len(), 6, 2).unwrap();
+            }
+            assert_eq!(ring.submit_and_wait(2).unwrap(), 2);
+
+            let mut seen = Vec::new();
+            while let Some(completion) = ring.pop_completion() {
+                seen.push((completion.user_data, completion.result.unwrap()));
+            }
+            seen.sort();
+
+            assert_eq!(seen, vec![(1, 5), (2, 8)]);
+            assert_eq!(&a, b"hello");
+            assert_eq!(&b, b"io_uring");
+            assert_eq!(ring.in_flight(), 0);
+
+            fs::remove_file(&path).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wg-utils-{}-{}", name, std::process::id()))
+    }
+
+    fn open_rw(path: &PathBuf) -> File {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_remap_tracks_file_growth() {
+        let path = temp_path("remap");
+        fs::write(&path, vec![1u8; 4096]).unwrap();
+
+        let mut map = unsafe { MemoryMappedFile::new(open_rw(&path), 4096, false) }.unwrap();
+        assert_eq!(map.len(), 4096);
+
+        let mut grown = vec![1u8; 4096];
+        grown.extend(vec![2u8; 4096]);
+        fs::write(&path, &grown).unwrap();
+
+        unsafe { map.remap(8192) }.unwrap();
+        assert_eq!(map.len(), 8192);
+        assert!(map.as_slice()[..4096].iter().all(|&b| b == 1));
+        assert!(map.as_slice()[4096..].iter().all(|&b| b == 2));
+
+        drop(map);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_remap_anonymous_keeps_contents() {
+        let mut map = unsafe { MemoryMappedFile::anonymous(4096) }.unwrap();
+        map.as_mut_slice()[..4].copy_from_slice(b"keep");
+
+        unsafe { map.remap(3 * 4096) }.unwrap();
+        assert_eq!(&map.as_slice()[..4], b"keep");
+        map.as_mut_slice()[3 * 4096 - 1] = 7;
+
+        unsafe { map.remap(4096) }.unwrap();
+        assert_eq!(map.len(), 4096);
+        assert_eq!(&map.as_slice()[..4], b"keep");
+    }
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    #[repr(C)]
+    struct Header {
+        magic: u32,
+        version: u16,
+        flags: u16,
+        length: u64,
+    }
+
+    #[test]
+    fn test_typed_read_write_at() {
+        let path = temp_path("typed");
+        fs::write(&path, vec![0u8; 4096]).unwrap();
+        let mut map = unsafe { MemoryMappedFile::new(open_rw(&path), 4096, true) }.unwrap();
+
+        let header = Header {
+            magic: 0xfeedface,
+            version: 3,
+            flags: 0x11,
+            length: 1 << 40,
+        };
+
+        for offset in [0, 1, 7, 100, 4096 - mem::size_of::<Header>()] {
+            map.write_at(offset, header);
+            assert_eq!(map.read_at::<Header>(offset), Some(header));
+        }
+
+        map.write_at(13, 42u64);
+        assert_eq!(map.read_at::<u64>(13), Some(42));
+        assert_eq!(map.read_at::<u8>(4095), Some(0));
+
+        assert_eq!(map.read_at::<u64>(4090), None);
+        assert_eq!(map.read_at::<Header>(4096), None);
+        assert_eq!(map.read_at::<u32>(usize::MAX), None);
+
+        drop(map);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapping_derefs_to_bytes() {
+        let mut map = unsafe { MemoryMappedFile::anonymous(64) }.unwrap();
+        map[0] = 7;
+        map[4..8].copy_from_slice(b"abcd");
+
+        assert_eq!(map[0], 7);
+        assert_eq!(&map[4..8], b"abcd");
+        assert_eq!(map.iter().filter(|&&b| b != 0).count(), 5);
+
+        fn checksum(bytes: impl AsRef<[u8]>) -> u64 {
+            bytes.as_ref().iter().map(|&b| b as u64).sum()
+        }
+        assert_eq!(
+            checksum(&map),
+            7 + b"abcd".iter().map(|&b| b as u64).sum::<u64>()
+        );
+        map.as_mut().fill(1);
+        assert_eq!(map.as_ref(), &[1; 64][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mapping is not writable")]
+    fn test_read_only_mapping_refuses_writes() {
+        let path = temp_path("read-only-map");
+        fs::write(&path, b"read only").unwrap();
+        let mut map = unsafe { MemoryMappedFile::from_path(&path, false) }.unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(&map[..4], b"read");
+        map[0] = b'R';
+    }
+
+    #[test]
+    fn test_from_path_maps_whole_file() {
+        let path = temp_path("from-path");
+        fs::write(&path, b"hello, mapping").unwrap();
+
+        {
+            let map = unsafe { MemoryMappedFile::from_path(&path, false) }.unwrap();
+            assert_eq!(map.len(), 14);
+            assert_eq!(&map[..5], b"hello");
+        }
+        {
+            let mut map = unsafe { MemoryMappedFile::from_path(&path, true) }.unwrap();
+            map[..5].copy_from_slice(b"HELLO");
+            map.sync(libc::MS_SYNC).unwrap();
+        }
+        assert_eq!(fs::read(&path).unwrap(), b"HELLO, mapping");
+
+        fs::write(&path, b"").unwrap();
+        let err = unsafe { MemoryMappedFile::from_path(&path, false) }
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        fs::remove_file(&path).unwrap();
+        let err = unsafe { MemoryMappedFile::from_path(&path, false) }
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    #[should_panic(expected = "Write out of bounds")]
+    fn test_write_at_out_of_bounds() {
+        let mut map = unsafe { MemoryMappedFile::anonymous(64) }.unwrap();
+        map.write_at(60, 0u64);
+    }
+
+    #[test]
+    fn test_lock_small_mapping() {
+        let mut map = unsafe { MemoryMappedFile::anonymous(4096) }.unwrap();
+
+        match map.lock() {
+            Ok(()) => {}
+            Err(err) if matches!(err.raw_os_error(), Some(libc::EPERM | libc::ENOMEM)) => {
+                eprintln!("skipping: mlock not permitted ({err})");
+                return;
+            }
+            Err(err) => panic!("mlock failed: {err}"),
+        }
+
+        map.as_mut_slice()[0] = 0xaa;
+        assert_eq!(map.as_slice()[0], 0xaa);
+        map.unlock().unwrap();
+    }
+
+    #[test]
+    fn test_anonymous_hugepage() {
+        let mut map = match unsafe { MemoryMappedFile::anonymous_hugepage(4096) } {
+            Ok(map) => map,
+            Err(err) => {
+                eprintln!("skipping: {err}");
+                return;
+            }
+        };
+
+        let len = map.len();
+        assert_eq!(len, 2 * 1024 * 1024);
+        map.as_mut_slice()[len - 1] = 1;
+        assert_eq!(map.as_slice()[len - 1], 1);
+    }
+
+    #[test]
+    fn test_sync_range() {
+        let path = temp_path("sync-range");
+        fs::write(&path, vec![0u8; 3 * 4096]).unwrap();
+        let mut map = unsafe { MemoryMappedFile::new(open_rw(&path), 3 * 4096, true) }.unwrap();
+
+        map.as_mut_slice()[4100..4108].copy_from_slice(b"checkpnt");
+        map.sync_range(4100, 8, libc::MS_SYNC).unwrap();
+        map.sync_range(0, 3 * 4096, libc::MS_SYNC).unwrap();
+        map.sync_range(3 * 4096, 0, libc::MS_SYNC).unwrap();
+        assert_eq!(&fs::read(&path).unwrap()[4100..4108], b"checkpnt");
+
+        let err = map.sync_range(3 * 4096 - 4, 8, libc::MS_SYNC).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(map.sync_range(usize::MAX, 2, libc::MS_SYNC).is_err());
+
+        drop(map);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_flock_exclusive() {
+        let path = temp_path("flock");
+        fs::write(&path, b"locked").unwrap();
+
+        let first = unsafe { RawIO::from_file(open_rw(&path)) };
+        let second = unsafe { RawIO::from_file(open_rw(&path)) };
+
+        first.lock_exclusive().unwrap();
+        assert!(!second.try_lock_exclusive().unwrap());
+        assert!(!second.try_lock_shared().unwrap());
+
+        first.unlock().unwrap();
+        assert!(second.try_lock_exclusive().unwrap());
+        second.unlock().unwrap();
+
+        first.lock_shared().unwrap();
+        assert!(second.try_lock_shared().unwrap());
+        assert!(!second.try_lock_exclusive().unwrap());
+
+        drop(first);
+        drop(second);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fadvise_willneed() {
+        let path = temp_path("fadvise");
+        fs::write(&path, vec![7u8; 8192]).unwrap();
+        let io = unsafe { RawIO::from_file(open_rw(&path)) };
+
+        io.fadvise(0, 0, FADV_WILLNEED).unwrap();
+        io.fadvise(0, 4096, FADV_SEQUENTIAL).unwrap();
+
+        let err = io.fadvise(0, 0, -1).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+
+        let mut buf = [0u8; 16];
+        assert_eq!(io.pread(&mut buf, 100).unwrap(), 16);
+        assert_eq!(buf, [7u8; 16]);
+
+        drop(io);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_splice_copy_between_regular_files() {
+        let (src_path, dst_path) = (temp_path("splice-src"), temp_path("splice-dst"));
+        let data: Vec<u8> = (0..300_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        fs::write(&src_path, &data).unwrap();
+        fs::write(&dst_path, b"").unwrap();
+
+        let src = unsafe { RawIO::from_file(File::open(&src_path).unwrap()) };
+        let dst = unsafe { RawIO::from_file(open_rw(&dst_path)) };
+
+        // asking for more than the file holds stops at end of input
+        assert_eq!(splice_copy(&src, &dst, 1 << 20).unwrap(), data.len() as u64);
+        assert_eq!(fs::read(&dst_path).unwrap(), data);
+
+        // a partial copy moves exactly `len` bytes
+        src.seek(0, libc::SEEK_SET).unwrap();
+        dst.truncate(0).unwrap();
+        dst.seek(0, libc::SEEK_SET).unwrap();
+        assert_eq!(splice_copy(&src, &dst, 100_000).unwrap(), 100_000);
+        assert_eq!(fs::read(&dst_path).unwrap(), &data[..100_000]);
+
+        drop((src, dst));
+        fs::remove_file(&src_path).unwrap();
+        fs::remove_file(&dst_path).unwrap();
+    }
+
+    #[test]
+    fn test_direct_copy_retries_after_signals() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::thread;
+        use std::time::Duration;
+
+        extern "C" fn ignore(_: libc::c_int) {}
+
+        // stops the signalling thread and puts the old SIGUSR1 handler back,
+        // even when an assertion below panics
+        struct RestoreOnDrop {
+            previous: libc::sigaction,
+            done: Arc<AtomicBool>,
+            signaller: Option<thread::JoinHandle<()>>,
+        }
+        impl Drop for RestoreOnDrop {
+            fn drop(&mut self) {
+                self.done.store(true, Ordering::Relaxed);
+                if let Some(signaller) = self.signaller.take() {
+                    let _ = signaller.join();
+                }
+                unsafe { libc::sigaction(libc::SIGUSR1, &self.previous, ptr::null_mut()) };
+            }
+        }
+
+        // a handler without SA_RESTART makes blocked reads fail with EINTR
+        let mut action: libc::sigaction = unsafe { mem::zeroed() };
+        action.sa_sigaction = ignore as *const () as usize;
+        let mut previous: libc::sigaction = unsafe { mem::zeroed() };
+        assert_eq!(
+            unsafe { libc::sigaction(libc::SIGUSR1, &action, &mut previous) },
+            0
+        );
+        let done = Arc::new(AtomicBool::new(false));
+        let mut guard = RestoreOnDrop {
+            previous,
+            done: Arc::clone(&done),
+            signaller: None,
+        };
+
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) }, 0);
+        let src = unsafe { RawIO::from_raw_fd(fds[0], true) };
+        let pipe_w = unsafe { RawIO::from_raw_fd(fds[1], true) };
+
+        let data: Vec<u8> = (0..256 * 1024u32).map(|i| (i % 253) as u8).collect();
+        let writer = {
+            let data = data.clone();
+            thread::spawn(move || {
+                // trickle the data in so the reader keeps blocking
+                for chunk in data.chunks(4096) {
+                    let mut written = 0;
+                    while written < chunk.len() {
+                        match unsafe {
+                            pipe_w.write_direct(chunk[written..].as_ptr(), chunk.len() - written)
+                        } {
+                            Ok(n) => written += n,
+                            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                            // the reader gave up; let the assertions report it
+                            Err(_) => return,
+                        }
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        };
+
+        let target = unsafe { libc::pthread_self() };
+        guard.signaller = Some(thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                unsafe { libc::pthread_kill(target, libc::SIGUSR1) };
+                thread::sleep(Duration::from_micros(300));
+            }
+        }));
+
+        let dst_path = temp_path("direct-copy-eintr");
+        let dst = unsafe { RawIO::from_file(File::create(&dst_path).unwrap()) };
+        let copied = direct_copy(&src, &dst, 8192);
+        drop(src);
+
+        drop(guard);
+        writer.join().unwrap();
+
+        assert_eq!(copied.unwrap(), data.len() as u64);
+        assert_eq!(fs::read(&dst_path).unwrap(), data);
+
+        drop(dst);
+        fs::remove_file(&dst_path).unwrap();
+    }
+
+    #[test]
+    fn test_pwritev_preadv_at_offset() {
+        let path = temp_path("pvectored");
+        fs::write(&path, vec![0u8; 64]).unwrap();
+        let io = unsafe { RawIO::from_file(open_rw(&path)) };
+
+        let (a, b, c) = (*b"page", *b"-header-", *b"body");
+        let out = [&a[..], &b[..], &c[..]].map(|buf| libc::iovec {
+            iov_base: buf.as_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        });
+        assert_eq!(io.pwritev(&out, 20).unwrap(), 16);
+
+        // scatter back into differently sized buffers
+        let (mut x, mut y) = ([0u8; 10], [0u8; 6]);
+        let mut input = [&mut x[..], &mut y[..]].map(|buf| libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        });
+        assert_eq!(io.preadv(&mut input, 20).unwrap(), 16);
+        assert_eq!((&x, &y), (b"page-heade", b"r-body"));
+
+        // neither call moved the file position
+        assert_eq!(io.seek(0, libc::SEEK_CUR).unwrap(), 0);
+        let contents = fs::read(&path).unwrap();
+        assert_eq!(&contents[20..36], b"page-header-body");
+        assert!(contents[..20]
+            .iter()
+            .chain(&contents[36..])
+            .all(|&b| b == 0));
+
+        drop(io);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_into_raw_fd_releases_ownership() {
+        let path = temp_path("into-raw-fd");
+        fs::write(&path, b"still open").unwrap();
+
+        let io = unsafe { RawIO::from_file(File::open(&path).unwrap()) };
+        let fd = io.into_raw_fd();
+
+        // had `into_raw_fd` closed it, this would fail with EBADF
+        let mut buf = [0u8; 10];
+        assert_eq!(
+            unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, 10) },
+            10
+        );
+        assert_eq!(&buf, b"still open");
+
+        // the new owner closes it exactly once
+        drop(unsafe { <File as std::os::unix::io::FromRawFd>::from_raw_fd(fd) });
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_dup_and_dup2_share_the_file() {
+        let path = temp_path("dup");
+        fs::write(&path, b"0123456789").unwrap();
+
+        let io = unsafe { RawIO::from_file(File::open(&path).unwrap()) };
+        let copy = io.dup().unwrap();
+        assert_ne!(copy.raw_fd(), io.raw_fd());
+        let fd_flags = unsafe { libc::fcntl(copy.raw_fd(), libc::F_GETFD) };
+        assert_ne!(fd_flags & libc::FD_CLOEXEC, 0);
+        let err = unsafe { io.dup2(io.raw_fd()) }.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        // the offset is shared, so reads continue where the other left off
+        let mut buf = [0u8; 4];
+        assert_eq!(unsafe { io.read_direct(buf.as_mut_ptr(), 4) }.unwrap(), 4);
+        assert_eq!(unsafe { copy.read_direct(buf.as_mut_ptr(), 4) }.unwrap(), 4);
+        assert_eq!(&buf, b"4567");
+
+        // dup2 onto a descriptor we own, replacing what it pointed at
+        let other = unsafe { RawIO::from_file(File::open("/dev/null").unwrap()) };
+        let target = other.into_raw_fd();
+        let redirected = unsafe { copy.dup2(target) }.unwrap();
+        assert_eq!(redirected.raw_fd(), target);
+        drop((io, copy));
+
+        let mut rest = [0u8; 4];
+        assert_eq!(
+            unsafe { redirected.read_direct(rest.as_mut_ptr(), 4) }.unwrap(),
+            2
+        );
+        assert_eq!(&rest[..2], b"89");
+
+        drop(redirected);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_nonblocking_socketpair_would_block() {
+        let mut fds = [0; 2];
+        assert_eq!(
+            unsafe {
+                libc::socketpair(
+                    libc::AF_UNIX,
+                    libc::SOCK_STREAM | libc::SOCK_CLOEXEC,
+                    0,
+                    fds.as_mut_ptr(),
+                )
+            },
+            0
+        );
+        let (a, b) = unsafe {
+            (
+                RawIO::from_raw_fd(fds[0], true),
+                RawIO::from_raw_fd(fds[1], true),
+            )
+        };
+
+        a.set_nonblocking(true).unwrap();
+        let flags = unsafe { libc::fcntl(a.raw_fd(), libc::F_GETFL) };
+        assert_ne!(flags & libc::O_NONBLOCK, 0);
+
+        // nothing to read yet: EWOULDBLOCK surfaces as WouldBlock
+        let mut buf = [0u8; 8];
+        let err = unsafe { a.read_direct(buf.as_mut_ptr(), buf.len()) }.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(err.raw_os_error(), Some(libc::EWOULDBLOCK));
+
+        // fill the socket buffer until the write side would block too
+        b.set_nonblocking(true).unwrap();
+        let chunk = [0u8; 4096];
+        let err = loop {
+            match unsafe { b.write_direct(chunk.as_ptr(), chunk.len()) } {
+                Ok(_) => continue,
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        assert_eq!(
+            unsafe { a.read_direct(buf.as_mut_ptr(), buf.len()) }.unwrap(),
+            8
+        );
+
+        a.set_nonblocking(false).unwrap();
+        let flags = unsafe { libc::fcntl(a.raw_fd(), libc::F_GETFL) };
+        assert_eq!(flags & libc::O_NONBLOCK, 0);
+    }
+
+    #[test]
+    fn test_stat_reports_written_size() {
+        let path = temp_path("stat");
+        let data = vec![7u8; 10_000];
+        fs::write(&path, &data).unwrap();
+
+        let io = unsafe { RawIO::from_file(File::open(&path).unwrap()) };
+        let meta = io.stat().unwrap();
+        assert_eq!(meta.size, 10_000);
+        assert!(meta.blksize.is_power_of_two());
+        assert_eq!(meta.mode & libc::S_IFMT, libc::S_IFREG);
+
+        // a zero buffer size picks the block size and still copies everything
+        let copy_path = temp_path("stat-copy");
+        let dst = unsafe { RawIO::from_file(File::create(&copy_path).unwrap()) };
+        assert_eq!(direct_copy(&io, &dst, 0).unwrap(), 10_000);
+        assert_eq!(dst.stat().unwrap().size, 10_000);
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&copy_path).unwrap();
+    }
+}
@@ -4,10 +4,17 @@
 //! These functions use unsafe Rust and should be used with extreme caution.
 //! Improper use can lead to undefined behavior, memory corruption, and security vulnerabilities.
 
-use std::alloc::{self, Layout};
+use std::alloc::{self, Layout, LayoutError};
+use std::error::Error;
+use std::fmt;
+use std::io;
 use std::mem;
 use std::ptr;
 
+/// Size in bytes of a cache line on the targets we care about (x86_64 and
+/// most aarch64 cores). Align per-thread data to this to avoid false sharing.
+pub const CACHE_LINE: usize = 64;
+
 /// Allocates uninitialized memory with the specified size and alignment.
 ///
 /// # Safety
@@ -25,10 +32,54 @@ pub unsafe fn allocate(size: usize, align: usize) -> *mut u8 {
         return ptr::null_mut();
     }
 
+    debug_assert!(align.is_power_of_two(), "Alignment must be a power of two");
     let layout = Layout::from_size_align_unchecked(size, align);
     alloc::alloc(layout)
 }
 
+/// Allocates zero-initialized memory with the specified size and alignment.
+///
+/// Prefer this over `allocate` followed by `fast_memset(ptr, 0, size)`: the
+/// allocator can hand back pages that are already zeroed by the kernel, and
+/// only clears the block itself for alignments `calloc` can't serve.
+///
+/// # Safety
+/// The caller must deallocate the memory using `deallocate` with the same size
+/// and alignment when no longer needed.
+///
+/// # Arguments
+/// * `size` - The size in bytes to allocate
+/// * `align` - The memory alignment (must be a power of 2)
+///
+/// # Returns
+/// A pointer to the zeroed memory block or null if allocation failed or
+/// `align` is not a power of 2
+pub unsafe fn allocate_zeroed(size: usize, align: usize) -> *mut u8 {
+    if size == 0 {
+        return ptr::null_mut();
+    }
+
+    match Layout::from_size_align(size, align) {
+        Ok(layout) => alloc::alloc_zeroed(layout),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Allocates uninitialized memory aligned to the start of a cache line.
+///
+/// # Safety
+/// Same requirements as `allocate`; free with `deallocate(ptr, size, CACHE_LINE)`.
+///
+/// # Arguments
+/// * `size` - The size in bytes to allocate
+///
+/// # Returns
+/// A `CACHE_LINE`-aligned pointer to the allocated memory block or null if
+/// allocation failed
+pub unsafe fn allocate_cache_aligned(size: usize) -> *mut u8 {
+    allocate(size, CACHE_LINE)
+}
+
 /// Deallocates memory previously allocated with `allocate`.
 ///
 /// # Safety
@@ -61,13 +112,76 @@ pub unsafe fn fast_memcpy(dst: *mut u8, src: *const u8, count: usize) {
         return;
     }
 
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            memcpy_avx2(dst, src, count);
+        } else {
+            memcpy_sse2(dst, src, count);
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    memcpy_words(dst, src, count);
+}
+
+/// Copies 32-byte chunks through AVX2 registers. The final chunk is realigned
+/// to end exactly at `count`, overlapping the previous one, so no byte tail
+/// loop is needed. Requires `count >= 32`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn memcpy_avx2(dst: *mut u8, src: *const u8, count: usize) {
+    use std::arch::x86_64::{__m256i, _mm256_loadu_si256, _mm256_storeu_si256};
+
+    let mut i = 0;
+    while i + 32 <= count {
+        let v = _mm256_loadu_si256(src.add(i) as *const __m256i);
+        _mm256_storeu_si256(dst.add(i) as *mut __m256i, v);
+        i += 32;
+    }
+
+    if i < count {
+        let last = count - 32;
+        let v = _mm256_loadu_si256(src.add(last) as *const __m256i);
+        _mm256_storeu_si256(dst.add(last) as *mut __m256i, v);
+    }
+}
+
+/// SSE2 variant of `memcpy_avx2`, two 16-byte registers per 32-byte chunk.
+/// SSE2 is part of the x86_64 baseline so this needs no runtime check.
+#[cfg(target_arch = "x86_64")]
+unsafe fn memcpy_sse2(dst: *mut u8, src: *const u8, count: usize) {
+    use std::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_storeu_si128};
+
+    let copy32 = |offset: usize| {
+        let a = _mm_loadu_si128(src.add(offset) as *const __m128i);
+        let b = _mm_loadu_si128(src.add(offset + 16) as *const __m128i);
+        _mm_storeu_si128(dst.add(offset) as *mut __m128i, a);
+        _mm_storeu_si128(dst.add(offset + 16) as *mut __m128i, b);
+    };
+
+    let mut i = 0;
+    while i + 32 <= count {
+        copy32(i);
+        i += 32;
+    }
+
+    if i < count {
+        copy32(count - 32);
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+unsafe fn memcpy_words(dst: *mut u8, src: *const u8, count: usize) {
     let dst_ptr = dst as *mut usize;
     let src_ptr = src as *const usize;
     let word_size = mem::size_of::<usize>();
     let word_count = count / word_size;
 
     for i in 0..word_count {
-        *dst_ptr.add(i) = *src_ptr.add(i);
+        dst_ptr
+            .add(i)
+            .write_unaligned(src_ptr.add(i).read_unaligned());
     }
 
     let remaining_offset = word_count * word_size;
@@ -76,6 +190,59 @@ pub unsafe fn fast_memcpy(dst: *mut u8, src: *const u8, count: usize) {
     }
 }
 
+/// Memory copy that tolerates overlapping regions, like C `memmove`.
+///
+/// # Safety
+/// The caller must ensure:
+/// * Both source and destination pointers point to valid memory regions
+/// * The memory regions are at least `count` bytes in size
+///
+/// Unlike `fast_memcpy`, the regions may overlap. When they do and `dst` lies
+/// after `src`, the copy runs backward so no source byte is overwritten before
+/// it has been read.
+///
+/// # Arguments
+/// * `dst` - Destination pointer
+/// * `src` - Source pointer
+/// * `count` - Number of bytes to copy
+pub unsafe fn fast_memmove(dst: *mut u8, src: *const u8, count: usize) {
+    let (d, s) = (dst as usize, src as usize);
+    if count == 0 || d == s {
+        return;
+    }
+
+    if d + count <= s || s + count <= d {
+        fast_memcpy(dst, src, count);
+        return;
+    }
+
+    let word_size = mem::size_of::<usize>();
+
+    if d < s {
+        let mut i = 0;
+        while i + word_size <= count {
+            let word = (src.add(i) as *const usize).read_unaligned();
+            (dst.add(i) as *mut usize).write_unaligned(word);
+            i += word_size;
+        }
+        while i < count {
+            *dst.add(i) = *src.add(i);
+            i += 1;
+        }
+    } else {
+        let mut i = count;
+        while i >= word_size {
+            i -= word_size;
+            let word = (src.add(i) as *const usize).read_unaligned();
+            (dst.add(i) as *mut usize).write_unaligned(word);
+        }
+        while i > 0 {
+            i -= 1;
+            *dst.add(i) = *src.add(i);
+        }
+    }
+}
+
 /// Fast memory set that uses SIMD instructions when available.
 ///
 /// # Safety
@@ -103,6 +270,8 @@ pub unsafe fn fast_memset(dst: *mut u8, value: u8, count: usize) {
         i *= 2;
     }
 
+    // [0, i) is filled and i > count / 2, so the remaining count - i bytes
+    // fit in the filled prefix and the copy below never overlaps itself.
     if i < count {
         ptr::copy_nonoverlapping(dst, dst.add(i), count - i);
     }
@@ -120,7 +289,20 @@ pub unsafe fn fast_memset(dst: *mut u8, value: u8, count: usize) {
 /// * `ptr` - Pointer to memory to clear
 /// * `count` - Number of bytes to clear
 pub unsafe fn secure_zero_memory(ptr: *mut u8, count: usize) {
-    for i in 0..count {
+    // bytes up to the first word boundary, then whole words, then the tail;
+    // every store is volatile so none of them can be elided
+    let head = ptr.align_offset(mem::align_of::<usize>()).min(count);
+    for i in 0..head {
+        ptr::write_volatile(ptr.add(i), 0);
+    }
+
+    let words = (count - head) / mem::size_of::<usize>();
+    let word_ptr = ptr.add(head) as *mut usize;
+    for i in 0..words {
+        ptr::write_volatile(word_ptr.add(i), 0);
+    }
+
+    for i in head + words * mem::size_of::<usize>()..count {
         ptr::write_volatile(ptr.add(i), 0);
     }
     std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
@@ -164,18 +346,61 @@ pub struct MemoryBlock {
     align: usize,
 }
 
+/// Why a `MemoryBlock` could not be created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllocError {
+    /// `align` is not a power of two, or `size` overflows once rounded up to
+    /// it.
+    Layout(LayoutError),
+    ZeroSize,
+    OutOfMemory,
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllocError::Layout(err) => write!(f, "invalid layout: {err}"),
+            AllocError::ZeroSize => write!(f, "cannot allocate a zero-sized block"),
+            AllocError::OutOfMemory => write!(f, "out of memory"),
+        }
+    }
+}
+
+impl Error for AllocError {}
+
 impl MemoryBlock {
-    pub fn new(size: usize, align: usize) -> Option<Self> {
-        unsafe {
-            let ptr = allocate(size, align);
-            if ptr.is_null() {
-                None
-            } else {
-                Some(Self { ptr, size, align })
-            }
+    pub fn new(size: usize, align: usize) -> Result<Self, AllocError> {
+        Self::alloc_with(size, align, allocate)
+    }
+
+    pub fn new_zeroed(size: usize, align: usize) -> Result<Self, AllocError> {
+        Self::alloc_with(size, align, allocate_zeroed)
+    }
+
+    // validates the layout first, since `allocate` builds it unchecked
+    fn alloc_with(
+        size: usize,
+        align: usize,
+        alloc: unsafe fn(usize, usize) -> *mut u8,
+    ) -> Result<Self, AllocError> {
+        Layout::from_size_align(size, align).map_err(AllocError::Layout)?;
+        if size == 0 {
+            return Err(AllocError::ZeroSize);
+        }
+
+        let ptr = unsafe { alloc(size, align) };
+        if ptr.is_null() {
+            Err(AllocError::OutOfMemory)
+        } else {
+            Ok(Self { ptr, size, align })
         }
     }
 
+    /// Allocates a block starting on a cache-line boundary.
+    pub fn new_cache_aligned(size: usize) -> Result<Self, AllocError> {
+        Self::new(size, CACHE_LINE)
+    }
+
     pub fn as_ptr(&self) -> *mut u8 {
         self.ptr
     }
@@ -190,16 +415,21 @@ impl MemoryBlock {
         }
     }
 
-    pub fn resize(&mut self, new_size: usize) -> bool {
-        unsafe {
-            let new_ptr = reallocate(self.ptr, self.size, new_size, self.align);
-            if new_ptr.is_null() {
-                return false;
-            }
-            self.ptr = new_ptr;
-            self.size = new_size;
-            true
+    /// Grows or shrinks the block, keeping its alignment and the contents up
+    /// to the smaller size. On error the block is left unchanged.
+    pub fn resize(&mut self, new_size: usize) -> Result<(), AllocError> {
+        Layout::from_size_align(new_size, self.align).map_err(AllocError::Layout)?;
+        if new_size == 0 {
+            return Err(AllocError::ZeroSize);
+        }
+
+        let new_ptr = unsafe { reallocate(self.ptr, self.size, new_size, self.align) };
+        if new_ptr.is_null() {
+            return Err(AllocError::OutOfMemory);
         }
+        self.ptr = new_ptr;
+        self.size = new_size;
+        Ok(())
     }
 
     pub fn secure_zero(&mut self) {
@@ -207,6 +437,29 @@ impl MemoryBlock {
             secure_zero_memory(self.ptr, self.size);
         }
     }
+
+    /// Pins the block's pages in RAM so they are never swapped to disk.
+    ///
+    /// Pair with `secure_zero` for key material. Locking can fail with
+    /// `ENOMEM` when the block exceeds `RLIMIT_MEMLOCK`.
+    pub fn lock(&self) -> io::Result<()> {
+        let ret = unsafe { libc::mlock(self.ptr as *const libc::c_void, self.size) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Releases a lock taken with `lock`.
+    pub fn unlock(&self) -> io::Result<()> {
+        let ret = unsafe { libc::munlock(self.ptr as *const libc::c_void, self.size) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Drop for MemoryBlock {
@@ -217,6 +470,262 @@ impl Drop for MemoryBlock {
     }
 }
 
+/// A bump allocator that carves allocations out of a chain of `MemoryBlock`s.
+///
+/// Individual allocations are never freed; `reset` releases everything at
+/// once and keeps the blocks around for reuse.
+pub struct Arena {
+    blocks: Vec<MemoryBlock>,
+    block_size: usize,
+    current: usize,
+    cursor: usize,
+}
+
+impl Arena {
+    const BLOCK_ALIGN: usize = 16;
+
+    /// Creates an empty arena that grows in blocks of `block_size` bytes.
+    /// No memory is allocated until the first call to `alloc`.
+    pub fn new(block_size: usize) -> Self {
+        Self {
+            blocks: Vec::new(),
+            block_size: block_size.max(1),
+            current: 0,
+            cursor: 0,
+        }
+    }
+
+    /// Returns a pointer to `size` bytes aligned to `align`, valid until the
+    /// next `reset` or until the arena is dropped.
+    ///
+    /// Requests larger than the block size get a dedicated block. Zero-sized
+    /// requests return a dangling, well-aligned pointer. Returns null if a new
+    /// block can't be allocated.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of 2.
+    pub fn alloc(&mut self, size: usize, align: usize) -> *mut u8 {
+        assert!(align.is_power_of_two(), "Alignment must be a power of 2");
+
+        if size == 0 {
+            return align as *mut u8;
+        }
+
+        while self.current < self.blocks.len() {
+            if let Some(ptr) = self.bump(size, align) {
+                return ptr;
+            }
+            self.current += 1;
+            self.cursor = 0;
+        }
+
+        let block_size = match size.checked_add(align - 1) {
+            Some(needed) => needed.max(self.block_size),
+            None => return ptr::null_mut(),
+        };
+        let Ok(block) = MemoryBlock::new(block_size, Self::BLOCK_ALIGN) else {
+            return ptr::null_mut();
+        };
+
+        self.blocks.push(block);
+        self.current = self.blocks.len() - 1;
+        self.cursor = 0;
+        self.bump(size, align).unwrap_or(ptr::null_mut())
+    }
+
+    /// Frees every allocation at once. The blocks are kept and reused by
+    /// subsequent allocations.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.cursor = 0;
+    }
+
+    /// Total bytes reserved across all blocks.
+    pub fn capacity(&self) -> usize {
+        self.blocks.iter().map(MemoryBlock::size).sum()
+    }
+
+    fn bump(&mut self, size: usize, align: usize) -> Option<*mut u8> {
+        let block = &self.blocks[self.current];
+        let base = block.as_ptr() as usize;
+        let start = (base + self.cursor).checked_add(align - 1)? & !(align - 1);
+        let end = start.checked_add(size)?;
+
+        if end > base + block.size() {
+            return None;
+        }
+
+        self.cursor = end - base;
+        Some(unsafe { block.as_ptr().add(start - base) })
+    }
+}
+
+/// A fixed-capacity pool of `T`-sized slots carved from one `MemoryBlock`.
+///
+/// Free slots form a singly linked list whose next-free index is stored in
+/// the slot itself, so `acquire` and `release` are both O(1). Slots are handed
+/// out uninitialized and the pool never drops the values stored in them.
+pub struct Pool<T> {
+    block: MemoryBlock,
+    slot_size: usize,
+    capacity: usize,
+    free_head: usize,
+    available: usize,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T> Pool<T> {
+    const END: usize = usize::MAX;
+
+    /// Pre-allocates `capacity` slots. Returns `None` if the allocation fails
+    /// or `capacity` is zero.
+    pub fn new(capacity: usize) -> Option<Self> {
+        let align = mem::align_of::<T>().max(mem::align_of::<usize>());
+        let size = mem::size_of::<T>().max(mem::size_of::<usize>());
+        let slot_size = size.checked_add(align - 1)? & !(align - 1);
+        let block = MemoryBlock::new(slot_size.checked_mul(capacity)?, align).ok()?;
+
+        for i in 0..capacity {
+            let next = if i + 1 < capacity { i + 1 } else { Self::END };
+            unsafe { (block.as_ptr().add(i * slot_size) as *mut usize).write(next) };
+        }
+
+        Some(Self {
+            block,
+            slot_size,
+            capacity,
+            free_head: 0,
+            available: capacity,
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    /// Takes a slot off the free list, or `None` if the pool is exhausted.
+    pub fn acquire(&mut self) -> Option<*mut T> {
+        if self.free_head == Self::END {
+            return None;
+        }
+
+        let slot = self.slot(self.free_head);
+        self.free_head = unsafe { (slot as *const usize).read() };
+        self.available -= 1;
+        Some(slot as *mut T)
+    }
+
+    /// Returns a slot obtained from `acquire` to the free list. Any value
+    /// left in the slot is overwritten without being dropped.
+    ///
+    /// # Panics
+    /// Panics if `ptr` does not point at a slot of this pool.
+    pub fn release(&mut self, ptr: *mut T) {
+        let offset = (ptr as usize).wrapping_sub(self.block.as_ptr() as usize);
+        assert!(
+            offset < self.block.size() && offset.is_multiple_of(self.slot_size),
+            "Pointer does not belong to this pool"
+        );
+
+        unsafe { (ptr as *mut usize).write(self.free_head) };
+        self.free_head = offset / self.slot_size;
+        self.available += 1;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of slots that can still be acquired.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    fn slot(&self, index: usize) -> *mut u8 {
+        unsafe { self.block.as_ptr().add(index * self.slot_size) }
+    }
+}
+
+/// A `MemoryBlock` holding `len` values of type `T`, sized and aligned for `T`.
+pub struct TypedBlock<T: Copy> {
+    block: MemoryBlock,
+    len: usize,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Copy> TypedBlock<T> {
+    /// Allocates room for `count` values and initializes each to `value`.
+    ///
+    /// Returns `None` if the allocation fails or the total size is zero.
+    pub fn new(count: usize, value: T) -> Option<Self> {
+        let size = count.checked_mul(mem::size_of::<T>())?;
+        let block = MemoryBlock::new(size, mem::align_of::<T>()).ok()?;
+
+        let ptr = block.as_ptr() as *mut T;
+        for i in 0..count {
+            unsafe { ptr.add(i).write(value) };
+        }
+
+        Some(Self {
+            block,
+            len: count,
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.block.as_ptr() as *const T, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.block.as_ptr() as *mut T, self.len) }
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.as_slice().get(index).copied()
+    }
+
+    /// Stores `value` at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "Index out of bounds");
+        self.as_mut_slice()[index] = value;
+    }
+}
+
+macro_rules! endian_accessors {
+    ($($t:ty => $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident;)*) => {
+        $(
+            #[doc = concat!("Reads a little-endian `", stringify!($t), "` from the offset.")]
+            pub fn $read_le(&self, offset: usize) -> $t {
+                <$t>::from_le_bytes(self.read(offset))
+            }
+
+            #[doc = concat!("Reads a big-endian `", stringify!($t), "` from the offset.")]
+            pub fn $read_be(&self, offset: usize) -> $t {
+                <$t>::from_be_bytes(self.read(offset))
+            }
+
+            #[doc = concat!("Writes `value` as a little-endian `", stringify!($t), "` at the offset.")]
+            pub fn $write_le(&mut self, offset: usize, value: $t) {
+                self.write(offset, value.to_le_bytes());
+            }
+
+            #[doc = concat!("Writes `value` as a big-endian `", stringify!($t), "` at the offset.")]
+            pub fn $write_be(&mut self, offset: usize, value: $t) {
+                self.write(offset, value.to_be_bytes());
+            }
+        )*
+    };
+}
+
 #[derive(Debug)]
 pub struct MemoryAccess<'a> {
     ptr: *mut u8,
@@ -243,11 +752,16 @@ impl<'a> MemoryAccess<'a> {
     /// # Panics
     /// Panics if the read would go out of bounds.
     pub fn read<T: Copy>(&self, offset: usize) -> T {
-        assert!(
-            offset + mem::size_of::<T>() <= self.size,
-            "Read out of bounds"
-        );
-        unsafe { ptr::read_unaligned(self.ptr.add(offset) as *const T) }
+        self.try_read(offset).expect("Read out of bounds")
+    }
+
+    /// Reads a value of type T from the offset, or `None` if the read would
+    /// go out of bounds.
+    pub fn try_read<T: Copy>(&self, offset: usize) -> Option<T> {
+        if !self.in_bounds(offset, mem::size_of::<T>()) {
+            return None;
+        }
+        Some(unsafe { ptr::read_unaligned(self.ptr.add(offset) as *const T) })
     }
 
     /// Writes a value of type T at the offset.
@@ -256,7 +770,7 @@ impl<'a> MemoryAccess<'a> {
     /// Panics if the write would go out of bounds.
     pub fn write<T>(&mut self, offset: usize, value: T) {
         assert!(
-            offset + mem::size_of::<T>() <= self.size,
+            self.in_bounds(offset, mem::size_of::<T>()),
             "Write out of bounds"
         );
         unsafe {
@@ -264,13 +778,32 @@ impl<'a> MemoryAccess<'a> {
         }
     }
 
+    /// Writes a value of type T at the offset, or returns `None` without
+    /// writing if the write would go out of bounds.
+    pub fn try_write<T>(&mut self, offset: usize, value: T) -> Option<()> {
+        if !self.in_bounds(offset, mem::size_of::<T>()) {
+            return None;
+        }
+        unsafe {
+            ptr::write_unaligned(self.ptr.add(offset) as *mut T, value);
+        }
+        Some(())
+    }
+
     /// Gets a slice of the memory.
     ///
     /// # Panics
     /// Panics if the slice would go out of bounds.
     pub fn slice(&self, offset: usize, len: usize) -> &[u8] {
-        assert!(offset + len <= self.size, "Slice out of bounds");
-        unsafe { std::slice::from_raw_parts(self.ptr.add(offset), len) }
+        self.try_slice(offset, len).expect("Slice out of bounds")
+    }
+
+    /// Gets a slice of the memory, or `None` if it would go out of bounds.
+    pub fn try_slice(&self, offset: usize, len: usize) -> Option<&[u8]> {
+        if !self.in_bounds(offset, len) {
+            return None;
+        }
+        Some(unsafe { std::slice::from_raw_parts(self.ptr.add(offset), len) })
     }
 
     /// Gets a mutable slice of the memory.
@@ -278,7 +811,410 @@ impl<'a> MemoryAccess<'a> {
     /// # Panics
     /// Panics if the slice would go out of bounds.
     pub fn slice_mut(&mut self, offset: usize, len: usize) -> &mut [u8] {
-        assert!(offset + len <= self.size, "Slice out of bounds");
+        assert!(self.in_bounds(offset, len), "Slice out of bounds");
         unsafe { std::slice::from_raw_parts_mut(self.ptr.add(offset), len) }
     }
+
+    // Fixed byte-order accessors for parsing on-disk formats. Like `read` and
+    // `write`, these panic if the access would go out of bounds.
+    endian_accessors! {
+        u32 => read_u32_le, read_u32_be, write_u32_le, write_u32_be;
+        u64 => read_u64_le, read_u64_be, write_u64_le, write_u64_be;
+        i32 => read_i32_le, read_i32_be, write_i32_le, write_i32_be;
+        i64 => read_i64_le, read_i64_be, write_i64_le, write_i64_be;
+    }
+
+    // `offset + len` can wrap for huge offsets, so never compare the raw sum
+    fn in_bounds(&self, offset: usize, len: usize) -> bool {
+        offset.checked_add(len).is_some_and(|end| end <= self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fast_memset_every_count() {
+        for count in 0..=512 {
+            for value in [0x00, 0x5a, 0xff] {
+                let mut got = vec![0xa5u8; 520];
+                let mut want = got.clone();
+
+                unsafe { fast_memset(got.as_mut_ptr().add(3), value, count) };
+                for byte in &mut want[3..3 + count] {
+                    *byte = value;
+                }
+
+                assert_eq!(got, want, "count {count} value {value:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_memmove_shifts_in_place() {
+        let original: Vec<u8> = (0..300u32).map(|i| (i * 7 % 256) as u8).collect();
+
+        for count in [0, 1, 5, 8, 9, 31, 64, 100, 200] {
+            for shift in [1, 3, 8, 13, 50] {
+                // shift right: dst after src
+                let mut got = original.clone();
+                let mut want = original.clone();
+                unsafe {
+                    fast_memmove(got.as_mut_ptr().add(shift), got.as_ptr(), count);
+                    ptr::copy(want.as_ptr(), want.as_mut_ptr().add(shift), count);
+                }
+                assert_eq!(got, want, "right count {count} shift {shift}");
+
+                // shift left: dst before src
+                let mut got = original.clone();
+                let mut want = original.clone();
+                unsafe {
+                    fast_memmove(got.as_mut_ptr(), got.as_ptr().add(shift), count);
+                    ptr::copy(want.as_ptr().add(shift), want.as_mut_ptr(), count);
+                }
+                assert_eq!(got, want, "left count {count} shift {shift}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_memcpy_matches_copy_nonoverlapping() {
+        let src: Vec<u8> = (0..1200u32).map(|i| (i * 31 % 256) as u8).collect();
+
+        for count in [0, 1, 7, 31, 32, 33, 63, 64, 65, 100, 255, 256, 1000] {
+            for src_off in [0, 1, 3] {
+                for dst_off in [0, 5, 16] {
+                    let mut got = vec![0xeeu8; 1100];
+                    let mut want = got.clone();
+
+                    unsafe {
+                        fast_memcpy(
+                            got.as_mut_ptr().add(dst_off),
+                            src.as_ptr().add(src_off),
+                            count,
+                        );
+                        ptr::copy_nonoverlapping(
+                            src.as_ptr().add(src_off),
+                            want.as_mut_ptr().add(dst_off),
+                            count,
+                        );
+                    }
+
+                    assert_eq!(got, want, "count {count} src+{src_off} dst+{dst_off}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_allocate_zeroed() {
+        for (size, align) in [(1, 1), (100, 8), (4096, 64), (1 << 20, 4096)] {
+            unsafe {
+                let ptr = allocate_zeroed(size, align);
+                assert!(!ptr.is_null());
+                assert_eq!(ptr as usize % align, 0);
+                assert!(std::slice::from_raw_parts(ptr, size)
+                    .iter()
+                    .all(|&b| b == 0));
+                deallocate(ptr, size, align);
+            }
+        }
+
+        assert!(unsafe { allocate_zeroed(0, 8) }.is_null());
+        assert!(unsafe { allocate_zeroed(64, 3) }.is_null());
+
+        let block = MemoryBlock::new_zeroed(512, 32).unwrap();
+        let bytes = unsafe { std::slice::from_raw_parts(block.as_ptr(), block.size()) };
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_arena_alloc_and_reset() {
+        let mut arena = Arena::new(1024);
+        let mut ptrs = Vec::new();
+
+        for i in 0..10_000u64 {
+            let ptr = arena.alloc(mem::size_of::<u64>(), mem::align_of::<u64>()) as *mut u64;
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % mem::align_of::<u64>(), 0);
+            unsafe { ptr.write(i) };
+            ptrs.push(ptr);
+        }
+
+        for (i, ptr) in ptrs.iter().enumerate() {
+            assert_eq!(unsafe { ptr.read() }, i as u64);
+        }
+
+        let capacity = arena.capacity();
+        assert!(capacity >= 10_000 * mem::size_of::<u64>());
+
+        arena.reset();
+        for (i, &old) in ptrs.iter().enumerate() {
+            let ptr = arena.alloc(mem::size_of::<u64>(), mem::align_of::<u64>()) as *mut u64;
+            assert_eq!(ptr, old, "allocation {i} did not reuse its slot");
+        }
+        assert_eq!(arena.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_arena_alignment_and_large_requests() {
+        let mut arena = Arena::new(256);
+
+        for align in [1, 2, 8, 64, 256] {
+            arena.alloc(3, 1);
+            let ptr = arena.alloc(10, align);
+            assert_eq!(ptr as usize % align, 0);
+        }
+
+        let big = arena.alloc(4096, 128);
+        assert!(!big.is_null());
+        assert_eq!(big as usize % 128, 0);
+        unsafe { fast_memset(big, 0xab, 4096) };
+
+        let empty = arena.alloc(0, 32);
+        assert_eq!(empty as usize % 32, 0);
+    }
+
+    #[test]
+    fn test_pool_exhaust_and_reuse() {
+        let mut pool: Pool<[u64; 3]> = Pool::new(64).unwrap();
+        let mut slots = Vec::new();
+
+        while let Some(ptr) = pool.acquire() {
+            assert_eq!(ptr as usize % mem::align_of::<[u64; 3]>(), 0);
+            unsafe { ptr.write([slots.len() as u64; 3]) };
+            slots.push(ptr);
+        }
+
+        assert_eq!(slots.len(), 64);
+        assert_eq!(pool.available(), 0);
+        for (i, ptr) in slots.iter().enumerate() {
+            assert_eq!(unsafe { ptr.read() }, [i as u64; 3]);
+        }
+
+        pool.release(slots[10]);
+        pool.release(slots[42]);
+        assert_eq!(pool.available(), 2);
+        assert_eq!(pool.acquire(), Some(slots[42]));
+        assert_eq!(pool.acquire(), Some(slots[10]));
+        assert_eq!(pool.acquire(), None);
+    }
+
+    #[test]
+    fn test_pool_small_slots() {
+        let mut pool: Pool<u8> = Pool::new(4).unwrap();
+        let a = pool.acquire().unwrap();
+        let b = pool.acquire().unwrap();
+
+        assert!(b as usize - a as usize >= mem::size_of::<usize>());
+        assert!(Pool::<u8>::new(0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Pointer does not belong to this pool")]
+    fn test_pool_release_foreign_pointer() {
+        let mut pool: Pool<u32> = Pool::new(4).unwrap();
+        let mut other = 0u32;
+        pool.release(&mut other);
+    }
+
+    #[test]
+    fn test_memory_access_offset_overflow() {
+        let mut buf = [0u8; 16];
+        let mut access = unsafe { MemoryAccess::new(buf.as_mut_ptr(), buf.len()) };
+        let offset = usize::MAX - 1;
+
+        assert_eq!(access.try_read::<u32>(offset), None);
+        assert_eq!(access.try_write(offset, 7u32), None);
+        assert!(access.try_slice(offset, 4).is_none());
+
+        access.write(12, 0xdead_beefu32);
+        assert_eq!(access.try_read::<u32>(12), Some(0xdead_beef));
+        assert_eq!(access.try_read::<u32>(13), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Read out of bounds")]
+    fn test_memory_access_read_overflow_panics() {
+        let mut buf = [0u8; 16];
+        let access = unsafe { MemoryAccess::new(buf.as_mut_ptr(), buf.len()) };
+        access.read::<u32>(usize::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slice out of bounds")]
+    fn test_memory_access_slice_overflow_panics() {
+        let mut buf = [0u8; 16];
+        let mut access = unsafe { MemoryAccess::new(buf.as_mut_ptr(), buf.len()) };
+        access.slice_mut(usize::MAX - 1, 4);
+    }
+
+    #[test]
+    fn test_memory_access_endian() {
+        let mut buf = [0u8; 32];
+        let mut access = unsafe { MemoryAccess::new(buf.as_mut_ptr(), buf.len()) };
+
+        access.write_u32_be(1, 0x0102_0304);
+        assert_eq!(access.slice(1, 4), &[0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(access.read_u32_be(1), 0x0102_0304);
+        assert_eq!(access.read_u32_le(1), 0x0403_0201);
+
+        access.write_u64_le(8, 0x0102_0304_0506_0708);
+        assert_eq!(access.slice(8, 8), &[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(access.read_u64_be(8), 0x0807_0605_0403_0201);
+
+        access.write_i32_be(16, -2);
+        assert_eq!(access.slice(16, 4), &[0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(access.read_i32_le(16), -16_777_217);
+
+        access.write_i64_le(24, i64::MIN);
+        assert_eq!(access.read_i64_le(24), i64::MIN);
+        assert_eq!(access.read_i64_be(24), 0x80);
+    }
+
+    #[test]
+    #[should_panic(expected = "Write out of bounds")]
+    fn test_memory_access_endian_out_of_bounds() {
+        let mut buf = [0u8; 8];
+        let mut access = unsafe { MemoryAccess::new(buf.as_mut_ptr(), buf.len()) };
+        access.write_u64_be(1, 0);
+    }
+
+    #[test]
+    fn test_cache_aligned() {
+        for size in [1, 8, 63, 64, 65, 1000] {
+            unsafe {
+                let ptr = allocate_cache_aligned(size);
+                assert!(!ptr.is_null());
+                assert_eq!(ptr as usize % 64, 0);
+                deallocate(ptr, size, CACHE_LINE);
+            }
+
+            let block = MemoryBlock::new_cache_aligned(size).unwrap();
+            assert_eq!(block.as_ptr() as usize % 64, 0);
+        }
+    }
+
+    #[test]
+    fn test_secure_zero_unaligned_ranges() {
+        // every head/word/tail split, checking the bytes around the range too
+        for offset in 0..mem::size_of::<usize>() {
+            for len in 0..40 {
+                let mut buf = [0xaau8; 64];
+                unsafe { secure_zero_memory(buf.as_mut_ptr().add(offset), len) };
+
+                for (i, byte) in buf.iter().enumerate() {
+                    let value = unsafe { ptr::read_volatile(byte) };
+                    let zeroed = (offset..offset + len).contains(&i);
+                    assert_eq!(
+                        value,
+                        if zeroed { 0 } else { 0xaa },
+                        "offset {offset}, len {len}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_memory_block_errors() {
+        assert!(matches!(
+            MemoryBlock::new(64, 3),
+            Err(AllocError::Layout(_))
+        ));
+        assert!(matches!(
+            MemoryBlock::new_zeroed(64, 0),
+            Err(AllocError::Layout(_))
+        ));
+        // rounding up to the alignment would exceed isize::MAX
+        assert!(matches!(
+            MemoryBlock::new(usize::MAX - 8, 16),
+            Err(AllocError::Layout(_))
+        ));
+        assert_eq!(MemoryBlock::new(0, 8).err(), Some(AllocError::ZeroSize));
+
+        // a valid layout that no allocator can satisfy
+        let err = MemoryBlock::new(1 << 62, 8).err().unwrap();
+        assert_eq!(err, AllocError::OutOfMemory);
+        assert_eq!(err.to_string(), "out of memory");
+    }
+
+    #[test]
+    fn test_lock_memory_block() {
+        let mut block = MemoryBlock::new(256, 16).unwrap();
+
+        match block.lock() {
+            Ok(()) => {}
+            Err(err) if matches!(err.raw_os_error(), Some(libc::EPERM | libc::ENOMEM)) => {
+                eprintln!("skipping: mlock not permitted ({err})");
+                return;
+            }
+            Err(err) => panic!("mlock failed: {err}"),
+        }
+
+        block.fill(0x5a);
+        block.secure_zero();
+        block.unlock().unwrap();
+    }
+
+    #[test]
+    fn test_typed_block_roundtrip() {
+        let mut block = TypedBlock::<u64>::new(100, 0).unwrap();
+        assert_eq!(block.len(), 100);
+        assert_eq!(
+            block.as_slice().as_ptr() as usize % mem::align_of::<u64>(),
+            0
+        );
+        assert!(block.as_slice().iter().all(|&v| v == 0));
+
+        for i in 0..block.len() {
+            block.set(i, (i as u64) * (i as u64));
+        }
+        for (i, v) in block.as_mut_slice().iter_mut().enumerate().skip(90) {
+            *v = i as u64;
+        }
+
+        assert_eq!(block.get(7), Some(49));
+        assert_eq!(block.get(95), Some(95));
+        assert_eq!(block.get(100), None);
+        assert_eq!(block.as_slice()[..4], [0, 1, 4, 9]);
+    }
+
+    #[test]
+    fn test_typed_block_rejects_empty_and_overflow() {
+        assert!(TypedBlock::<u32>::new(0, 1).is_none());
+        assert!(TypedBlock::<u64>::new(usize::MAX, 1).is_none());
+    }
+
+    #[test]
+    fn test_resize_validates_size() {
+        let mut block = MemoryBlock::new(64, 16).unwrap();
+        block.fill(0xAB);
+
+        block.resize(256).unwrap();
+        assert_eq!(block.size(), 256);
+        assert_eq!(block.as_ptr() as usize % 16, 0);
+        assert!(unsafe { std::slice::from_raw_parts(block.as_ptr(), 64) }
+            .iter()
+            .all(|&b| b == 0xAB));
+
+        // rejected sizes leave the block as it was
+        assert_eq!(block.resize(0), Err(AllocError::ZeroSize));
+        assert!(matches!(
+            block.resize(usize::MAX - 8),
+            Err(AllocError::Layout(_))
+        ));
+        assert_eq!(block.size(), 256);
+
+        block.resize(8).unwrap();
+        assert_eq!(unsafe { *block.as_ptr().add(7) }, 0xAB);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    fn test_typed_block_set_out_of_bounds() {
+        let mut block = TypedBlock::<f64>::new(4, 0.0).unwrap();
+        block.set(4, 1.0);
+    }
 }